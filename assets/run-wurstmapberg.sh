@@ -11,5 +11,6 @@ echo "updating Rust..."
 echo "updating wurstmapberg CLI..."
 .cargo/bin/cargo install-update --all --git
 echo "rendering map..."
+# renders the Overworld into out/overworld/ relative to the working directory; see the README for the output layout and options
 .cargo/bin/wurstmapberg-cli /opt/wurstmineberg/maps/wurstmineberg/world/world
 echo "done."
@@ -6,6 +6,7 @@ use {
         borrow::Cow,
         collections::HashMap,
         convert::Infallible as Never,
+        fmt,
         fs::File,
         io::prelude::*,
         num::NonZero,
@@ -281,6 +282,40 @@ enum BlockMapColor {
         dry: MapColor,
         wet: MapColor,
     },
+    Slab {
+        color: MapColor,
+    },
+    Stairs {
+        color: MapColor,
+    },
+    Thin {
+        color: MapColor,
+    },
+    Layers {
+        color: MapColor,
+    },
+    Biome {
+        color: MapColor,
+        kind: BiomeColorKind,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum BiomeColorKind {
+    Grass,
+    Foliage,
+    Water,
+}
+
+// variant names overlap with MapColor, so they're qualified in this crate's output
+impl fmt::Debug for BiomeColorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Grass => write!(f, "BiomeColorKind::Grass"),
+            Self::Foliage => write!(f, "BiomeColorKind::Foliage"),
+            Self::Water => write!(f, "BiomeColorKind::Water"),
+        }
+    }
 }
 
 fn parse_dye_color(source: &[u8], dye_color: &tree_sitter::Node<'_>) -> Result<MapColor, Error> {
@@ -330,6 +365,7 @@ fn parse_map_color(source: &[u8], defs: &HashMap<String, BlockMapColor>, color:
                             BlockMapColor::Crops { growing, .. } => growing.to_owned(),
                             BlockMapColor::Pillar { top, .. } => top.to_owned(),
                             BlockMapColor::Waterloggable { dry, .. } => dry.to_owned(),
+                            BlockMapColor::Slab { color } | BlockMapColor::Stairs { color } | BlockMapColor::Thin { color } | BlockMapColor::Layers { color } | BlockMapColor::Biome { color, .. } => color.to_owned(),
                         }
                     }
                     kind => return Err(Error::NodeKind("defaultMapColor", kind.to_owned())),
@@ -653,7 +689,7 @@ impl<I: Iterator> From<itertools::ExactlyOneError<I>> for Error {
 #[wheel::main]
 async fn main() -> Result<(), Error> {
     let block_colors = get_block_colors().await?;
-    let mut f = File::create("crate/wurstmapberg-cli/src/colors.rs")?;
+    let mut f = File::create("crate/wurstmapberg/src/colors.rs")?;
     writeln!(&mut f, "use {{")?;
     writeln!(&mut f, "    std::collections::HashMap,")?;
     writeln!(&mut f, "    collect_mac::collect,")?;
@@ -661,13 +697,26 @@ async fn main() -> Result<(), Error> {
     writeln!(&mut f, "    crate::{{")?;
     writeln!(&mut f, "        BlockMapColor::*,")?;
     writeln!(&mut f, "        MapColor::*,")?;
+    writeln!(&mut f, "        biome::BiomeColorKind,")?;
     writeln!(&mut f, "    }},")?;
     writeln!(&mut f, "}};")?;
     writeln!(&mut f)?;
     writeln!(&mut f, concat!("/// Up to date as of Minecraft ", env!("CARGO_PKG_VERSION")))?;
-    writeln!(&mut f, "pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {{")?;
+    writeln!(&mut f, "pub(crate) fn vanilla_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {{")?;
     writeln!(&mut f, "    collect![")?;
     for (id, color) in block_colors.into_iter().sorted_by(|(id1, _), (id2, _)| id1.cmp(id2)) {
+        // partial-height blocks are only distinguished in the output so definitions copied from them stay unaffected
+        let color = match color {
+            BlockMapColor::Single(color) if id.ends_with("_slab") => BlockMapColor::Slab { color },
+            BlockMapColor::Single(color) if id.ends_with("_stairs") => BlockMapColor::Stairs { color },
+            BlockMapColor::Single(color) if id.ends_with("_carpet") || id.ends_with("_pressure_plate") || id == "lily_pad" => BlockMapColor::Thin { color },
+            BlockMapColor::Single(color) if id == "snow" => BlockMapColor::Layers { color },
+            //TODO generate from Java (net/minecraft/client/color/block/BlockColors.java)
+            BlockMapColor::Single(color) if matches!(&*id, "grass_block" | "short_grass" | "tall_grass" | "fern" | "large_fern" | "sugar_cane") => BlockMapColor::Biome { color, kind: BiomeColorKind::Grass },
+            BlockMapColor::Single(color) if matches!(&*id, "oak_leaves" | "jungle_leaves" | "acacia_leaves" | "dark_oak_leaves" | "mangrove_leaves" | "vine") => BlockMapColor::Biome { color, kind: BiomeColorKind::Foliage },
+            BlockMapColor::Single(color) if matches!(&*id, "water" | "bubble_column") => BlockMapColor::Biome { color, kind: BiomeColorKind::Water },
+            color => color,
+        };
         writeln!(&mut f, "        BlockId::{} => {color:?},", match format!("minecraft:{id}").parse::<mcanvil::BlockId>()? {
             mcanvil::BlockId::Other(id) => format!("Other({id:?}.to_owned())"),
             id => format!("{id:?}"),
@@ -3,268 +3,896 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 use {
     std::{
-        cmp::Ordering::*,
         collections::{
+            BTreeMap,
             BTreeSet,
             HashMap,
+            hash_map,
+        },
+        num::NonZero,
+        panic::{
+            self,
+            AssertUnwindSafe,
+        },
+        path::{
+            Path,
+            PathBuf,
         },
-        path::PathBuf,
         pin::pin,
-        sync::Arc,
+        str::FromStr,
+        sync::{
+            Arc,
+            atomic::{
+                AtomicUsize,
+                Ordering,
+            },
+        },
+        time::{
+            Duration,
+            Instant,
+            SystemTime,
+        },
+    },
+    chrono::{
+        DateTime,
+        Local,
     },
     futures::stream::{
         FuturesUnordered,
+        StreamExt as _,
         TryStreamExt as _,
     },
     image::{
+        DynamicImage,
         ImageError,
+        Pixel as _,
         Rgba,
         RgbaImage,
+        codecs::{
+            jpeg::JpegEncoder,
+            png::{
+                CompressionType,
+                FilterType,
+                PngEncoder,
+            },
+            webp::WebPEncoder,
+        },
+    },
+    indicatif::{
+        ProgressBar,
+        ProgressStyle,
+    },
+    log::{
+        debug,
+        error,
+        info,
+        warn,
     },
     mcanvil::{
+        BlockId,
         Dimension,
         Region,
         RegionDecodeError,
     },
     parking_lot::Mutex,
-    tokio::io,
-    wheel::fs,
+    serde::{
+        Deserialize,
+        Serialize,
+    },
+    tempfile::TempDir,
+    tokio::{
+        io,
+        sync::{
+            Semaphore,
+            oneshot,
+        },
+    },
+    wheel::{
+        fs,
+        traits::IoResultExt as _,
+    },
+    wurstmapberg::{
+        BlockMapColor,
+        Heightmap,
+        Hillshade,
+        Level,
+        LightKind,
+        MapItem,
+        Palette,
+        Player,
+        RenderedRegion,
+        Renderer,
+        SouthEdge,
+        TintMultipliers,
+    },
 };
 
-mod colors;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum MapColor {
-    None,
-    Grass,
-    Sand,
-    Wool,
-    Fire,
-    Ice,
-    Metal,
-    Plant,
-    Snow,
-    Clay,
-    Dirt,
-    Stone,
-    Water,
-    Wood,
-    Quartz,
-    ColorOrange,
-    ColorMagenta,
-    ColorLightBlue,
-    ColorYellow,
-    ColorLightGreen,
-    ColorPink,
-    ColorGray,
-    ColorLightGray,
-    ColorCyan,
-    ColorPurple,
-    ColorBlue,
-    ColorBrown,
-    ColorGreen,
-    ColorRed,
-    ColorBlack,
-    Gold,
-    Diamond,
-    Lapis,
-    Emerald,
-    Podzol,
+#[derive(Clone)]
+enum DimensionArg {
+    Overworld,
     Nether,
-    TerracottaWhite,
-    TerracottaOrange,
-    TerracottaMagenta,
-    TerracottaLightBlue,
-    TerracottaYellow,
-    TerracottaLightGreen,
-    TerracottaPink,
-    TerracottaGray,
-    TerracottaLightGray,
-    TerracottaCyan,
-    TerracottaPurple,
-    TerracottaBlue,
-    TerracottaBrown,
-    TerracottaGreen,
-    TerracottaRed,
-    TerracottaBlack,
-    CrimsonNylium,
-    CrimsonStem,
-    CrimsonHyphae,
-    WarpedNylium,
-    WarpedStem,
-    WarpedHyphae,
-    WarpedWartBlock,
-    Deepslate,
-    RawIron,
-    GlowLichen,
-}
-
-enum Tint {
-    Dark,
-    Normal,
-    Light,
-}
-
-impl Tint {
-    fn multiplier(&self) -> u16 {
+    End,
+    All,
+    /// A dimension added by a datapack, stored in `dimensions/<namespace>/<path>` in the world directory.
+    Custom {
+        namespace: String,
+        path: String,
+    },
+}
+
+impl FromStr for DimensionArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Ok(match s {
+            "overworld" => Self::Overworld,
+            "nether" => Self::Nether,
+            "end" => Self::End,
+            "all" => Self::All,
+            _ => {
+                let Some((namespace, path)) = s.strip_prefix("custom:").and_then(|id| id.split_once(':')) else {
+                    return Err(format!("expected overworld, nether, end, all, or custom:<namespace>:<path> but got {s:?}"))
+                };
+                Self::Custom { namespace: namespace.to_owned(), path: path.to_owned() }
+            }
+        })
+    }
+}
+
+impl DimensionArg {
+    fn targets(&self, world_dir: &Path, has_ceiling: bool) -> Vec<DimensionTarget> {
+        let vanilla = |dimension| DimensionTarget {
+            dimension,
+            world_dir: world_dir.to_owned(),
+            name: dimension_dir_name(dimension).to_owned(),
+            ceiling: dimension == Dimension::Nether,
+            vanilla: true,
+        };
+        match self {
+            Self::Overworld => vec![vanilla(Dimension::Overworld)],
+            Self::Nether => vec![vanilla(Dimension::Nether)],
+            Self::End => vec![vanilla(Dimension::End)],
+            Self::All => vec![vanilla(Dimension::Overworld), vanilla(Dimension::Nether), vanilla(Dimension::End)],
+            Self::Custom { namespace, path } => vec![DimensionTarget {
+                // datapack dimensions use the same layout as the Overworld, inside their own directory
+                dimension: Dimension::Overworld,
+                world_dir: world_dir.join("dimensions").join(namespace).join(path),
+                name: format!("{namespace}/{path}"),
+                ceiling: has_ceiling,
+                vanilla: false,
+            }],
+        }
+    }
+}
+
+/// A dimension to render, along with where to find it.
+struct DimensionTarget {
+    /// The vanilla dimension whose directory layout is used to find the region files.
+    dimension: Dimension,
+    /// The directory that contains this dimension's region files in the layout of `dimension`.
+    world_dir: PathBuf,
+    /// The subdirectory of the output directory into which this dimension is rendered, also used in messages.
+    name: String,
+    ceiling: bool,
+    /// Whether this is the actual vanilla dimension `dimension` rather than a datapack dimension, so features that depend on the dimension like markers and slime chunks apply.
+    vanilla: bool,
+}
+
+/// The name of the subdirectory of the output directory into which the given dimension is rendered.
+fn dimension_dir_name(dimension: Dimension) -> &'static str {
+    match dimension {
+        Dimension::Overworld => "overworld",
+        Dimension::Nether => "nether",
+        Dimension::End => "end",
+    }
+}
+
+fn parse_hex_color(s: &str) -> Result<Rgba<u8>, String> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 { return Err(format!("expected a color in the form RRGGBB but got {s:?}")) }
+    let rgb = u32::from_str_radix(hex, 16).map_err(|e| format!("invalid color {s:?}: {e}"))?;
+    let [_, r, g, b] = rgb.to_be_bytes();
+    Ok(Rgba([r, g, b, u8::MAX]))
+}
+
+/// Parses a block ID from a `--colors` file. IDs without a namespace refer to vanilla blocks, like in commands.
+fn parse_block_id(name: &str) -> BlockId {
+    let Ok(id) = if name.contains(':') { name.parse() } else { format!("minecraft:{name}").parse() };
+    id
+}
+
+fn parse_since(s: &str) -> Result<SystemTime, String> {
+    DateTime::parse_from_rfc3339(s).map(SystemTime::from).map_err(|e| format!("invalid RFC 3339 timestamp {s:?}: {e}"))
+}
+
+fn parse_tint_multipliers(s: &str) -> Result<TintMultipliers, String> {
+    let values = s.split(',').map(|value| value.trim().parse::<u8>().map_err(|e| format!("invalid tint multiplier {value:?} (must be at most 255): {e}"))).collect::<Result<Vec<_>, _>>()?;
+    let defaults = TintMultipliers::default();
+    match *values {
+        [dark, normal, light] => Ok(TintMultipliers { dark, normal, light, ..defaults }),
+        [dark, normal, light, darkest] => Ok(TintMultipliers { darkest, dark, normal, light }),
+        _ => Err(format!("expected 3 or 4 comma-separated tint multipliers but got {}", values.len())),
+    }
+}
+
+fn parse_zoom(s: &str) -> Result<u32, String> {
+    let zoom = s.parse::<u32>().map_err(|e| format!("invalid zoom level {s:?}: {e}"))?;
+    if !zoom.is_power_of_two() || zoom > 16 * 32 { return Err(format!("zoom level must be a power of two up to 512 but got {zoom}")) }
+    Ok(zoom)
+}
+
+fn parse_altitude(s: &str) -> Result<f64, String> {
+    let altitude = s.parse::<f64>().map_err(|e| format!("invalid light altitude {s:?}: {e}"))?;
+    if !(altitude > 0.0 && altitude <= 90.0) { return Err(format!("light altitude must be greater than 0 and at most 90 degrees but got {altitude}")) }
+    Ok(altitude)
+}
+
+fn parse_gamma(s: &str) -> Result<f32, String> {
+    let gamma = s.parse::<f32>().map_err(|e| format!("invalid gamma {s:?}: {e}"))?;
+    if !(gamma > 0.0 && gamma.is_finite()) { return Err(format!("gamma must be a positive number but got {gamma}")) }
+    Ok(gamma)
+}
+
+/// Per-chunk information that's rendered into a separate set of images instead of onto the map.
+#[derive(Clone, Copy)]
+enum Overlay {
+    Inhabited,
+    ChunkStatus,
+    LightMap(LightKind),
+}
+
+impl Overlay {
+    /// The subdirectory of a dimension's output directory that this overlay's images are written to.
+    fn dir_name(self) -> &'static str {
+        match self {
+            Self::Inhabited => "inhabited",
+            Self::ChunkStatus => "status",
+            Self::LightMap(LightKind::Block) => "block-light",
+            Self::LightMap(LightKind::Sky) => "sky-light",
+        }
+    }
+
+    fn render(self, renderer: &Renderer, region: &Region) -> Result<RgbaImage, wurstmapberg::Error> {
+        match self {
+            Self::Inhabited => wurstmapberg::render_inhabited_time(region),
+            Self::ChunkStatus => wurstmapberg::render_chunk_status(region),
+            Self::LightMap(kind) => renderer.render_light_map(region, kind),
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum OutputFormat {
+    Png,
+    /// Like png, but palette-indexed if the image has at most 256 distinct colors, which makes it much smaller.
+    IndexedPng,
+    /// Lossy, without transparency.
+    Jpeg,
+    /// Lossless.
+    Webp,
+}
+
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Png | Self::IndexedPng => "png",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+        }
+    }
+
+    fn image_format(self) -> image::ImageFormat {
+        match self {
+            Self::Png | Self::IndexedPng => image::ImageFormat::Png,
+            Self::Jpeg => image::ImageFormat::Jpeg,
+            Self::Webp => image::ImageFormat::WebP,
+        }
+    }
+
+    /// Whether an image encoded in this format decodes to exactly the same pixels.
+    fn is_lossless(self) -> bool {
+        match self {
+            Self::Png | Self::IndexedPng | Self::Webp => true,
+            Self::Jpeg => false,
+        }
+    }
+
+    fn encode(self, img: &RgbaImage, EncodeOptions { quality, png_compression }: EncodeOptions) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::default();
         match self {
-            Self::Dark => 180,
-            Self::Normal => 220,
-            Self::Light => 255,
-        }
-    }
-}
-
-impl MapColor {
-    fn tint(&self, tint: Tint) -> Rgba<u8> {
-        let base_rgb = match self {
-            MapColor::None => return Rgba([0; 4]),
-            MapColor::Grass => 8368696_u32,
-            MapColor::Sand => 16247203,
-            MapColor::Wool => 13092807,
-            MapColor::Fire => 16711680,
-            MapColor::Ice => 10526975,
-            MapColor::Metal => 10987431,
-            MapColor::Plant => 31744,
-            MapColor::Snow => 16777215,
-            MapColor::Clay => 10791096,
-            MapColor::Dirt => 9923917,
-            MapColor::Stone => 7368816,
-            MapColor::Water => 4210943,
-            MapColor::Wood => 9402184,
-            MapColor::Quartz => 16776437,
-            MapColor::ColorOrange => 14188339,
-            MapColor::ColorMagenta => 11685080,
-            MapColor::ColorLightBlue => 6724056,
-            MapColor::ColorYellow => 15066419,
-            MapColor::ColorLightGreen => 8375321,
-            MapColor::ColorPink => 15892389,
-            MapColor::ColorGray => 5000268,
-            MapColor::ColorLightGray => 10066329,
-            MapColor::ColorCyan => 5013401,
-            MapColor::ColorPurple => 8339378,
-            MapColor::ColorBlue => 3361970,
-            MapColor::ColorBrown => 6704179,
-            MapColor::ColorGreen => 6717235,
-            MapColor::ColorRed => 10040115,
-            MapColor::ColorBlack => 1644825,
-            MapColor::Gold => 16445005,
-            MapColor::Diamond => 6085589,
-            MapColor::Lapis => 4882687,
-            MapColor::Emerald => 55610,
-            MapColor::Podzol => 8476209,
-            MapColor::Nether => 7340544,
-            MapColor::TerracottaWhite => 13742497,
-            MapColor::TerracottaOrange => 10441252,
-            MapColor::TerracottaMagenta => 9787244,
-            MapColor::TerracottaLightBlue => 7367818,
-            MapColor::TerracottaYellow => 12223780,
-            MapColor::TerracottaLightGreen => 6780213,
-            MapColor::TerracottaPink => 10505550,
-            MapColor::TerracottaGray => 3746083,
-            MapColor::TerracottaLightGray => 8874850,
-            MapColor::TerracottaCyan => 5725276,
-            MapColor::TerracottaPurple => 8014168,
-            MapColor::TerracottaBlue => 4996700,
-            MapColor::TerracottaBrown => 4993571,
-            MapColor::TerracottaGreen => 5001770,
-            MapColor::TerracottaRed => 9321518,
-            MapColor::TerracottaBlack => 2430480,
-            MapColor::CrimsonNylium => 12398641,
-            MapColor::CrimsonStem => 9715553,
-            MapColor::CrimsonHyphae => 6035741,
-            MapColor::WarpedNylium => 1474182,
-            MapColor::WarpedStem => 3837580,
-            MapColor::WarpedHyphae => 5647422,
-            MapColor::WarpedWartBlock => 1356933,
-            MapColor::Deepslate => 6579300,
-            MapColor::RawIron => 14200723,
-            MapColor::GlowLichen => 8365974,
+            Self::Png => img.write_with_encoder(png_compression.encoder(&mut buf))?,
+            Self::IndexedPng => if let Some(indexed) = encode_indexed_png(img, png_compression)? {
+                buf = indexed;
+            } else {
+                img.write_with_encoder(png_compression.encoder(&mut buf))?;
+            },
+            // JPEG has no alpha channel, so transparent areas are filled with black unless --background is specified
+            Self::Jpeg => DynamicImage::from(fill_background(img, Rgba([0, 0, 0, u8::MAX]))).into_rgb8().write_with_encoder(JpegEncoder::new_with_quality(&mut buf, quality))?,
+            Self::Webp => img.write_with_encoder(WebPEncoder::new_lossless(&mut buf))?,
+        }
+        Ok(buf)
+    }
+}
+
+/// Format-specific settings for encoding output images.
+#[derive(Clone, Copy)]
+struct EncodeOptions {
+    quality: u8,
+    png_compression: PngCompression,
+}
+
+/// How much effort to spend on compressing PNG output. Fast output is typically 1.5–2× as large as the default but encodes several times faster, which helps when rerendering repeatedly; small output saves another few percent at a much higher encoding time.
+#[derive(Clone, Copy)]
+enum PngCompression {
+    Fast,
+    Default,
+    Small,
+}
+
+impl PngCompression {
+    fn encoder<W: std::io::Write>(self, w: W) -> PngEncoder<W> {
+        match self {
+            Self::Fast => PngEncoder::new_with_quality(w, CompressionType::Fast, FilterType::NoFilter),
+            Self::Default => PngEncoder::new(w),
+            Self::Small => PngEncoder::new_with_quality(w, CompressionType::Best, FilterType::Adaptive),
+        }
+    }
+
+    fn apply(self, encoder: &mut png::Encoder<'_, impl std::io::Write>) {
+        match self {
+            Self::Fast => {
+                encoder.set_compression(png::Compression::Fast);
+                encoder.set_filter(png::Filter::NoFilter);
+            }
+            Self::Default => {}
+            Self::Small => {
+                encoder.set_compression(png::Compression::High);
+                encoder.set_filter(png::Filter::Adaptive);
+            }
+        }
+    }
+}
+
+/// Encodes an image as an 8-bit palette-indexed PNG, or returns `None` if it has more than 256 distinct colors.
+fn encode_indexed_png(img: &RgbaImage, compression: PngCompression) -> Result<Option<Vec<u8>>, png::EncodingError> {
+    let mut palette = Vec::<[u8; 4]>::default();
+    let mut indices = HashMap::<[u8; 4], u8>::default();
+    let mut data = Vec::with_capacity(img.width() as usize * img.height() as usize);
+    for pixel in img.pixels() {
+        let index = match indices.entry(pixel.0) {
+            hash_map::Entry::Occupied(entry) => *entry.get(),
+            hash_map::Entry::Vacant(entry) => {
+                let Ok(index) = u8::try_from(palette.len()) else { return Ok(None) };
+                palette.push(pixel.0);
+                *entry.insert(index)
+            }
         };
-        let [_, r, g, b] = base_rgb.to_be_bytes().map(|channel| (u16::from(channel) * tint.multiplier() / 255) as u8);
-        Rgba([r, g, b, u8::MAX])
+        data.push(index);
+    }
+    let mut buf = Vec::default();
+    let mut encoder = png::Encoder::new(&mut buf, img.width(), img.height());
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    compression.apply(&mut encoder);
+    encoder.set_palette(palette.iter().flat_map(|&[r, g, b, _]| [r, g, b]).collect::<Vec<_>>());
+    if palette.iter().any(|&[_, _, _, a]| a < u8::MAX) {
+        encoder.set_trns(palette.iter().map(|&[_, _, _, a]| a).collect::<Vec<_>>());
+    }
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(Some(buf))
+}
+
+/// Composites an image over a solid color.
+fn fill_background(img: &RgbaImage, background: Rgba<u8>) -> RgbaImage {
+    let mut filled = img.clone();
+    for pixel in filled.pixels_mut() {
+        let mut composited = background;
+        composited.blend(pixel);
+        *pixel = composited;
+    }
+    filled
+}
+
+/// What to fill areas that aren't on the map with, instead of leaving them transparent.
+#[derive(Clone, Copy)]
+enum Backdrop {
+    Color(Rgba<u8>),
+    /// A light and dark checkerboard like image editors use, so transparent areas can be told apart from black ones.
+    Checkerboard,
+}
+
+impl Backdrop {
+    fn fill(self, img: &RgbaImage) -> RgbaImage {
+        const SQUARE: u32 = 8;
+
+        match self {
+            Self::Color(color) => fill_background(img, color),
+            Self::Checkerboard => {
+                let mut filled = img.clone();
+                for (x, y, pixel) in filled.enumerate_pixels_mut() {
+                    let mut composited = if (x / SQUARE + y / SQUARE) % 2 == 0 { Rgba([255, 255, 255, u8::MAX]) } else { Rgba([204, 204, 204, u8::MAX]) };
+                    composited.blend(pixel);
+                    *pixel = composited;
+                }
+                filled
+            }
+        }
     }
 }
 
+/// How far to turn the map clockwise. Rendering, including shading, always happens in the game's orientation with north (negative Z) up, so the shading turns with the image.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Rotation {
+    #[value(name = "0")]
+    None,
+    #[value(name = "90")]
+    Quarter,
+    #[value(name = "180")]
+    Half,
+    #[value(name = "270")]
+    ThreeQuarters,
+}
+
+impl Rotation {
+    fn degrees(self) -> u16 {
+        match self {
+            Self::None => 0,
+            Self::Quarter => 90,
+            Self::Half => 180,
+            Self::ThreeQuarters => 270,
+        }
+    }
+
+    fn apply(self, img: RgbaImage) -> RgbaImage {
+        match self {
+            Self::None => img,
+            Self::Quarter => image::imageops::rotate90(&img),
+            Self::Half => image::imageops::rotate180(&img),
+            Self::ThreeQuarters => image::imageops::rotate270(&img),
+        }
+    }
+
+    /// Turns a point around the origin, with the same orientation as [`Rotation::apply`].
+    fn point(self, [x, z]: [i64; 2]) -> [i64; 2] {
+        match self {
+            Self::None => [x, z],
+            Self::Quarter => [-z, x],
+            Self::Half => [-x, -z],
+            Self::ThreeQuarters => [z, -x],
+        }
+    }
+
+    /// The position of the given region's image in the rotated grid of region images.
+    fn region(self, [x, z]: [i32; 2]) -> [i64; 2] {
+        let [x1, z1] = self.point([i64::from(x), i64::from(z)]);
+        let [x2, z2] = self.point([i64::from(x) + 1, i64::from(z) + 1]);
+        [x1.min(x2), z1.min(z2)]
+    }
+}
+
+/// Shrinks an image by combining each `zoom`×`zoom` square of pixels into one.
+///
+/// Colors are averaged as squares, which approximates averaging in linear light, and weighted by alpha so transparent pixels don't darken their neighbors. A pixel is only transparent if its entire square is.
+fn downscale(img: &RgbaImage, zoom: u32) -> RgbaImage {
+    RgbaImage::from_fn(img.width() / zoom, img.height() / zoom, |x, y| {
+        let mut sums = [0_u64; 4];
+        for dy in 0..zoom {
+            for dx in 0..zoom {
+                let Rgba([r, g, b, a]) = *img.get_pixel(x * zoom + dx, y * zoom + dy);
+                for (sum, channel) in sums.iter_mut().zip([r, g, b]) {
+                    *sum += u64::from(channel).pow(2) * u64::from(a);
+                }
+                sums[3] += u64::from(a);
+            }
+        }
+        let [r, g, b, a] = sums;
+        if a == 0 { return Rgba([0; 4]) }
+        let [r, g, b] = [r, g, b].map(|sum| (sum as f64 / a as f64).sqrt().round() as u8);
+        // rounded up so a square with any non-transparent pixel stays visible
+        Rgba([r, g, b, a.div_ceil(u64::from(zoom * zoom)) as u8])
+    })
+}
+
+/// Softens the outline between transparent and non-transparent areas by replacing each pixel on it with the average of its 3×3 neighborhood.
+///
+/// The average is weighted by alpha, so transparent pixels lower the alpha without bleeding their (meaningless) color into the result.
+fn smooth_edges(img: &RgbaImage) -> RgbaImage {
+    let (width, height) = img.dimensions();
+    let neighbors = |x: u32, y: u32| (y.saturating_sub(1)..(y + 2).min(height)).flat_map(move |ny| (x.saturating_sub(1)..(x + 2).min(width)).map(move |nx| img[(nx, ny)]));
+    RgbaImage::from_fn(width, height, |x, y| {
+        let pixel = img[(x, y)];
+        if neighbors(x, y).all(|neighbor| (neighbor[3] == 0) == (pixel[3] == 0)) { return pixel }
+        let mut sums = [0_u32; 4];
+        // pixels outside of the image count as transparent
+        let count = 9;
+        for Rgba([r, g, b, a]) in neighbors(x, y) {
+            for (sum, channel) in sums.iter_mut().zip([r, g, b]) {
+                *sum += u32::from(channel) * u32::from(a);
+            }
+            sums[3] += u32::from(a);
+        }
+        let [r, g, b, a] = sums;
+        if a == 0 { return Rgba([0; 4]) }
+        Rgba([(r / a) as u8, (g / a) as u8, (b / a) as u8, (a / count) as u8])
+    })
+}
+
+/// Multiplies the color channels of each pixel by `brightness` after applying `gamma`, leaving alpha untouched.
+fn adjust_colors(img: &mut RgbaImage, brightness: f32, gamma: f32) {
+    let lookup = std::array::from_fn::<u8, 256, _>(|channel| ((channel as f32 / 255.0).powf(1.0 / gamma) * brightness * 255.0).round().clamp(0.0, 255.0) as u8);
+    for Rgba([r, g, b, _]) in img.pixels_mut() {
+        for channel in [r, g, b] {
+            *channel = lookup[usize::from(*channel)];
+        }
+    }
+}
+
+/// The bounding box `[x, y, width, height]` of the non-transparent pixels of an image, or `None` if it's entirely transparent.
+fn content_bounds(img: &RgbaImage) -> Option<[u32; 4]> {
+    let mut bounds = None::<[u32; 4]>;
+    for (x, y, pixel) in img.enumerate_pixels() {
+        if pixel[3] == 0 { continue }
+        bounds = Some(match bounds {
+            Some([min_x, min_y, max_x, max_y]) => [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)],
+            None => [x, y, x, y],
+        });
+    }
+    bounds.map(|[min_x, min_y, max_x, max_y]| [min_x, min_y, max_x - min_x + 1, max_y - min_y + 1])
+}
+
+/// The contents of the sidecar file written next to each region image with `--crop-to-content`: the position of the cropped image's top left corner within the full region, in pixels.
+#[derive(Serialize, Deserialize)]
+struct CropOffset {
+    x: u32,
+    z: u32,
+}
+
+/// The contents of the `metadata.json` file written into the output directory of each dimension.
+#[derive(Serialize)]
+struct Metadata {
+    dimension: String,
+    /// The smallest and largest region coordinates as `[[min_x, min_z], [max_x, max_z]]`.
+    regions: [[i32; 2]; 2],
+    /// The smallest and largest block coordinates as `[[min_x, min_z], [max_x, max_z]]`.
+    blocks: [[i64; 2]; 2],
+    /// The size of the stitched map as `[width, height]`, in pixels.
+    pixels: [u64; 2],
+    /// The number of blocks along each axis that are combined into a single pixel.
+    zoom: u32,
+    /// How far the images are turned clockwise, in degrees. The other fields are unaffected, except `pixels`.
+    rotation: u16,
+    /// The highest `DataVersion` of the chunk columns that were rendered in this run, if any.
+    data_version: Option<i32>,
+    rendered_at: String,
+}
+
+/// The settings embedded into the `index.html` written by `--viewer`.
+#[derive(Serialize)]
+struct ViewerConfig {
+    /// The output subdirectories of the dimensions that have a `metadata.json`.
+    dimensions: Vec<String>,
+    extension: &'static str,
+    /// Whether to show the stitched world image instead of the individual region images.
+    stitched: bool,
+    /// Whether region images are cropped and have a crop offset file.
+    cropped: bool,
+}
+
+/// A single region (`x,z`) or an inclusive rectangle of regions (`x1,z1..x2,z2`), given in region coordinates.
 #[derive(Debug, Clone, Copy)]
-enum BlockMapColor {
-    Single(MapColor),
-    Bed {
-        head: MapColor,
-        foot: MapColor,
-    },
-    Crops {
-        growing: MapColor,
-        grown: MapColor,
-    },
-    Pillar {
-        top: MapColor,
-        side: MapColor,
-    },
-    Waterloggable {
-        dry: MapColor,
-        wet: MapColor,
-    },
+struct RegionRange {
+    min: [i32; 2],
+    max: [i32; 2],
 }
 
-const DIMENSION: Dimension = Dimension::Overworld;
+impl RegionRange {
+    fn contains(&self, [x, z]: [i32; 2]) -> bool {
+        (self.min[0]..=self.max[0]).contains(&x) && (self.min[1]..=self.max[1]).contains(&z)
+    }
+}
 
-static FALLBACK_HEIGHTMAP: &[[i32; 16]; 16] = &[[320; 16]; 16];
+impl FromStr for RegionRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        fn parse_coords(s: &str) -> Result<[i32; 2], String> {
+            let (x, z) = s.split_once(',').ok_or_else(|| format!("expected region coordinates in the form x,z but got {s:?}"))?;
+            Ok([
+                x.trim().parse().map_err(|e| format!("invalid x coordinate {x:?}: {e}"))?,
+                z.trim().parse().map_err(|e| format!("invalid z coordinate {z:?}: {e}"))?,
+            ])
+        }
+
+        Ok(if let Some((start, end)) = s.split_once("..") {
+            let [x1, z1] = parse_coords(start)?;
+            let [x2, z2] = parse_coords(end)?;
+            Self {
+                min: [x1.min(x2), z1.min(z2)],
+                max: [x1.max(x2), z1.max(z2)],
+            }
+        } else {
+            let coords = parse_coords(s)?;
+            Self { min: coords, max: coords }
+        })
+    }
+}
 
 #[derive(clap::Parser)]
 #[clap(version)]
 struct Args {
+    /// The world directory, or a .zip archive of it. Archives are extracted to a temporary directory first, so every region is considered changed.
     world_dir: PathBuf,
-    #[clap(default_value = "out")]
+    /// Region images are written into subdirectories of this directory, which is created if it doesn't exist.
+    #[clap(short, long = "output-dir", default_value = "out")]
     out_dir: PathBuf,
+    /// Which dimension to render: overworld, nether, end, all, or custom:<namespace>:<path> for a datapack dimension. Each dimension is rendered into its own subdirectory of the output directory.
+    #[clap(long, default_value = "overworld")]
+    dimension: DimensionArg,
+    /// Render a datapack dimension given via --dimension=custom:… like the Nether, starting below its bedrock roof.
+    #[clap(long)]
+    has_ceiling: bool,
+    /// Which heightmap to start scanning for the surface from. Not used in the Nether.
+    #[clap(long, value_enum, default_value = "world-surface")]
+    heightmap: Heightmap,
+    /// Render a horizontal slice of the world at this Y coordinate instead of the surface, e.g. to inspect caves.
+    #[clap(long, allow_hyphen_values = true, conflicts_with = "heightmap")]
+    y_level: Option<i32>,
+    /// Ignore blocks above this Y coordinate, e.g. to render the terrain below floating builds on creative servers.
+    #[clap(long, value_name = "Y", allow_hyphen_values = true)]
+    max_y: Option<i32>,
+    /// Ignore blocks below this Y coordinate, so deep excavations are left transparent (or filled with --background) instead of showing what's at the bottom.
+    #[clap(long, value_name = "Y", allow_hyphen_values = true)]
+    min_y: Option<i32>,
+    /// Skip past this many layers of solid ground before picking a block, to show the floors of caves.
+    #[clap(long, default_value_t = 0)]
+    cave_layers: u32,
+    /// Draw contour lines at multiples of this many blocks of elevation.
+    #[clap(long, value_name = "INTERVAL")]
+    contours: Option<NonZero<u16>>,
+    /// Shade drops of at least the given number of blocks (default 3) darker than smaller drops. Vanilla maps shade all drops the same.
+    #[clap(long, value_name = "BLOCKS", num_args = 0..=1, default_missing_value = "3", value_parser = clap::value_parser!(u16).range(1..))]
+    steep_shading: Option<u16>,
+    /// The brightness of the dark, normal, and light shading levels out of 255, and optionally of the darkest level used by --steep-shading. Defaults to Minecraft's values.
+    #[clap(long, value_name = "DARK,NORMAL,LIGHT[,DARKEST]", value_parser = parse_tint_multipliers, default_value = "180,220,255,135")]
+    tint_multipliers: TintMultipliers,
+    /// Shade based on the height difference to the west neighbor as well as the north neighbor, for a stronger relief effect. Vanilla maps only shade north to south.
+    #[clap(long)]
+    west_shading: bool,
+    /// Shade like a relief map, based on the slope of the terrain relative to a light source, instead of like the in-game map.
+    #[clap(long)]
+    hillshade: bool,
+    /// With --hillshade, the compass direction the light comes from, in degrees clockwise from north.
+    #[clap(long, value_name = "DEGREES", default_value_t = 315.0)]
+    light_azimuth: f64,
+    /// With --hillshade, the angle of the light above the horizon, in degrees.
+    #[clap(long, value_name = "DEGREES", default_value_t = 45.0, value_parser = parse_altitude)]
+    light_altitude: f64,
+    /// Highlight light sources emitting at least the given light level (default 1), e.g. to check coverage for mob-proofing.
+    #[clap(long, value_name = "MIN_LEVEL", num_args = 0..=1, default_missing_value = "1", value_parser = clap::value_parser!(u8).range(1..=15))]
+    lights: Option<u8>,
+    /// Tint chunks where slimes can spawn. Only applies to the Overworld.
+    #[clap(long)]
+    slime_chunks: bool,
+    /// Mark the world spawn point and outline the world border. Only applies to the Overworld.
+    #[clap(long)]
+    markers: bool,
+    /// Mark the last known position of each player, in a color derived from their UUID.
+    #[clap(long)]
+    players: bool,
+    /// Mark banners placed in the world, like on in-game maps.
+    #[clap(long)]
+    banners: bool,
+    /// Mark where structures like villages and strongholds start. Optionally takes a comma-separated list of structure types to mark, e.g. --structures=village,stronghold.
+    #[clap(long, value_name = "TYPES", num_args = 0..=1, require_equals = true, value_delimiter = ',')]
+    structures: Option<Vec<String>>,
+    /// Render blocks missing from the color table in magenta instead of skipping them, to find gaps in the palette.
+    #[clap(long)]
+    debug_unknown: bool,
+    /// Also render how long players have spent in each chunk into a separate set of images in an `inhabited` subdirectory.
+    #[clap(long)]
+    inhabited: bool,
+    /// Always draw blocks that emit full-strength light, like lava, fire, and glowstone, at full brightness regardless of shading.
+    #[clap(long)]
+    emissive: bool,
+    /// Draw stained glass and ice as a translucent layer over the block below them, unlike vanilla maps.
+    #[clap(long)]
+    transparent_blocks: bool,
+    /// Count the blocks scanned for each column, down to and including the rendered block, and write the counts per region to blocks.csv in the output directory of each dimension. Implies --force so the counts cover all regions.
+    #[clap(long)]
+    block_list: bool,
+    /// Instead of rendering, count every block in the world and write the totals, most common first, to census.csv in the output directory of each dimension. Much slower than rendering.
+    #[clap(long)]
+    census: bool,
+    /// Only check that every region and chunk column can be decoded, without rendering or writing anything.
+    #[clap(long)]
+    dry_run: bool,
+    /// Also render how far each chunk has been generated into a separate set of images in a `status` subdirectory.
+    #[clap(long)]
+    chunk_status: bool,
+    /// Also render the block or sky light level above the surface into a separate set of images in a `block-light` or `sky-light` subdirectory. Where block light is 0, which is where most hostile mobs can spawn, it's drawn in red.
+    #[clap(long, value_enum)]
+    light_map: Option<LightKind>,
+    /// Also render the in-game map items in the world's data directory, for comparison with the rendered map. They're written as PNG to a `maps` subdirectory of the output directory.
+    #[clap(long)]
+    map_items: bool,
+    /// The built-in color table to start from.
+    #[clap(long, value_enum, default_value = "vanilla")]
+    palette: Palette,
+    /// A JSON file mapping block IDs to colors, overriding or extending the built-in color table. IDs without a namespace default to `minecraft:`.
+    #[clap(long)]
+    colors: Option<PathBuf>,
+    /// Instead of rendering, list the vanilla blocks that have no color in the color table selected with --palette and --colors.
+    #[clap(long)]
+    palette_coverage: bool,
+    /// Only render the given region (`x,z`) or range of regions (`x1,z1..x2,z2`). May be specified multiple times.
+    #[clap(long = "region", allow_hyphen_values = true)]
+    region_filter: Vec<RegionRange>,
+    /// Draw region and chunk boundaries on top of the map.
+    #[clap(long)]
+    grid: bool,
+    /// The color of the lines drawn by --grid, as RRGGBB.
+    #[clap(long, value_parser = parse_hex_color, default_value = "000000")]
+    grid_color: Rgba<u8>,
+    /// Combine each N×N square of blocks into a single pixel, e.g. 2 for 256×256 pixel region images. Must be a power of two.
+    #[clap(long, value_name = "N", default_value = "1", value_parser = parse_zoom)]
+    zoom: u32,
+    /// Turn the map clockwise by this many degrees, e.g. 180 to put north at the bottom. Images of regions that aren't rerendered keep their old orientation, so use --force when changing this for an existing output directory.
+    #[clap(long, value_enum, default_value = "0")]
+    rotate: Rotation,
+    /// Rerender all regions, even those whose region file is older than the existing image.
+    #[clap(long)]
+    force: bool,
+    /// Only render regions whose region file was modified after this time, given in RFC 3339 format, e.g. 2024-05-01T12:00:00Z. Rendered regions are shaded against their north and west neighbors read from disk, whether or not those were modified. A region that wasn't modified isn't rerendered though, so its northernmost row and westernmost column keep their old shading if only its north or west neighbor was modified. Like with --region, the other regions are left out of metadata.json and the stitched image.
+    #[clap(long, value_parser = parse_since)]
+    since: Option<SystemTime>,
+    /// The image format of the output files.
+    #[clap(long, value_enum, default_value = "png")]
+    format: OutputFormat,
+    /// The quality of JPEG output, from 1 to 100.
+    #[clap(long, default_value_t = 90, value_parser = clap::value_parser!(u8).range(1..=100))]
+    quality: u8,
+    /// Compress PNG output quickly at the cost of larger files, e.g. while iterating on render settings.
+    #[clap(long)]
+    png_fast: bool,
+    /// Compress PNG output as much as possible at the cost of much slower encoding, e.g. for publishing.
+    #[clap(long, conflicts_with = "png_fast")]
+    png_small: bool,
+    /// Multiply the color channels of the map by this factor.
+    #[clap(long, default_value_t = 1.0)]
+    brightness: f32,
+    /// Apply this gamma correction to the colors of the map. Values above 1 brighten midtones.
+    #[clap(long, default_value_t = 1.0, value_parser = parse_gamma)]
+    gamma: f32,
+    /// Fill areas that aren't on the map with this color, as RRGGBB, instead of leaving them transparent. JPEG output defaults to black.
+    #[clap(long, value_parser = parse_hex_color)]
+    background: Option<Rgba<u8>>,
+    /// Debugging aid: fill areas that aren't on the map with a checkerboard pattern to check which pixels are transparent. Not intended for published maps.
+    #[clap(long, conflicts_with = "background")]
+    checkerboard: bool,
+    /// Crop each region image to its non-transparent pixels and write the offset of the cropped image to a JSON file next to it.
+    #[clap(long)]
+    crop_to_content: bool,
+    /// Also write images for regions that don't contain any rendered blocks. Without this, such regions only get an empty r.x.z.empty marker file, so later runs know they're up to date.
+    #[clap(long)]
+    emit_empty: bool,
+    /// Soften the outline of the rendered area in zoomed out region images and in the stitched world image.
+    #[clap(long)]
+    smooth_edges: bool,
+    /// After rendering, also combine all regions of each dimension into a single world image.
+    #[clap(long)]
+    stitch: bool,
+    /// After rendering, also pack the region images of each dimension into a map.mbtiles file with a tile pyramid down to a single tile, for tile servers and offline map apps.
+    #[clap(long)]
+    mbtiles: bool,
+    /// After rendering, also write an index.html into the output directory for browsing the map, e.g. via `python -m http.server`.
+    #[clap(long)]
+    viewer: bool,
+    /// The maximum number of regions to render at the same time. Defaults to the number of CPUs.
+    #[clap(short, long)]
+    jobs: Option<NonZero<usize>>,
+    /// The number of threads used for rendering, shared by all regions being rendered. Defaults to the number of CPUs.
+    #[clap(long)]
+    threads: Option<NonZero<usize>>,
+    /// Exit successfully and only warn about regions or chunk columns that failed to decode, as long as at least one of the regions that were loaded decoded successfully.
+    #[clap(long)]
+    continue_on_error: bool,
+    /// Copy each dimension's region files to a temporary directory before rendering, so a running server writing to them is less likely to cause torn reads. Needs as much free space as the largest dimension.
+    #[clap(long)]
+    copy_first: bool,
+    /// Log each region with a timestamp instead of showing a progress bar.
+    #[clap(short, long)]
+    verbose: bool,
+    /// Only log warnings and errors, without a progress bar. The RUST_LOG environment variable overrides this and --verbose, e.g. RUST_LOG=wurstmapberg_cli=debug.
+    #[clap(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+    /// In the Nether, columns are scanned starting at the first air gap below this Y coordinate.
+    #[clap(long, default_value_t = 127)]
+    nether_ceiling: i32,
+    /// In the Nether, render the lowest floor below the ceiling cutoff instead of the highest, with ledges and overhangs above it drawn translucently.
+    #[clap(long)]
+    nether_cutaway: bool,
 }
 
-fn format_region_col_errors(sample_region_error: Option<&RegionDecodeError>, sample_col_error: Option<&mcanvil::ChunkColumnDecodeError>) -> String {
-    match (sample_region_error, sample_col_error) {
-        (None, None) => format!("no error"),
-        (None, Some(col_error)) => format!("sample chunk column error: {col_error}"),
-        (Some(region_error), None) => format!("sample region error: {region_error}"),
-        (Some(region_error), Some(col_error)) => format!("sample region error: {region_error}; sample chunk column error: {col_error}"),
+/// Sorts errors by dimension in the order the dimensions are rendered, then by region coordinates, so error messages are reproducible.
+fn sorted_errors<E>(errors: HashMap<(usize, [i32; 2]), E>) -> Vec<((usize, [i32; 2]), E)> {
+    let mut errors = errors.into_iter().collect::<Vec<_>>();
+    errors.sort_by_key(|&(key, _)| key);
+    errors
+}
+
+fn format_region_col_errors(dimension_names: &[String], region_errors: &[((usize, [i32; 2]), RegionDecodeError)], col_errors: &[((usize, [i32; 2]), wurstmapberg::Error)]) -> String {
+    let mut summary = format!(
+        "failed to decode {} region{} and chunk columns in {} region{}",
+        region_errors.len(), if region_errors.len() == 1 { "" } else { "s" },
+        col_errors.len(), if col_errors.len() == 1 { "" } else { "s" },
+    );
+    for ((dimension, [x, z]), e) in region_errors {
+        summary.push_str(&format!("\nregion {} {x}, {z}: {e}", dimension_names[*dimension]));
+    }
+    for ((dimension, [x, z]), e) in col_errors {
+        summary.push_str(&format!("\nchunk column in region {} {x}, {z}: {e}", dimension_names[*dimension]));
     }
+    summary
 }
 
 #[derive(Debug, thiserror::Error)]
 enum Error {
     #[error(transparent)] Image(#[from] ImageError),
+    #[error(transparent)] Png(#[from] png::EncodingError),
+    #[error(transparent)] Sqlite(#[from] rusqlite::Error),
     #[error(transparent)] Task(#[from] tokio::task::JoinError),
+    #[error(transparent)] ThreadPool(#[from] rayon::ThreadPoolBuildError),
     #[error(transparent)] Wheel(#[from] wheel::Error),
+    #[error(transparent)] Wurstmapberg(#[from] wurstmapberg::Error),
+    #[error(transparent)] Zip(#[from] zip::result::ZipError),
+    #[error("failed to read or write crop offset: {0}")]
+    CropOffset(serde_json::Error),
+    #[error("failed to write metadata: {0}")]
+    Metadata(serde_json::Error),
+    #[error("failed to write viewer: {0}")]
+    Viewer(serde_json::Error),
+    #[error("failed to load color palette: {0}")]
+    Palette(serde_json::Error),
     #[error("failed to get list of regions: {0}")]
     ListRegions(RegionDecodeError),
-    #[error("a region that was listed has since been deleted")]
-    RegionNotFound,
-    #[error("{}", format_region_col_errors(.region_errors.values().next(), .col_errors.values().next()))]
+    #[error("the stitched image would be too large ({width}×{height} regions)")]
+    StitchTooLarge {
+        width: u64,
+        height: u64,
+    },
+    #[error("{}", format_region_col_errors(.dimension_names, .region_errors, .col_errors))]
     RegionsCols {
-        region_errors: HashMap<[i32; 2], RegionDecodeError>,
-        /// Note these are keyed by region coords, not chunk coords
-        col_errors: HashMap<[i32; 2], mcanvil::ChunkColumnDecodeError>,
+        /// The names of the rendered dimensions, indexed by the first part of the error keys.
+        dimension_names: Vec<String>,
+        /// Sorted using `sorted_errors`
+        region_errors: Vec<((usize, [i32; 2]), RegionDecodeError)>,
+        /// Sorted using `sorted_errors`. Note these are keyed by region coords, not chunk coords
+        col_errors: Vec<((usize, [i32; 2]), wurstmapberg::Error)>,
     },
 }
 
 impl wheel::CustomExit for Error {
     fn exit(self, cmd_name: &'static str) {
         match self {
-            Self::RegionsCols { region_errors, col_errors } => {
+            Self::RegionsCols { dimension_names, region_errors, col_errors } => {
                 if !region_errors.is_empty() {
-                    println!("failed to decode {} region{}:", region_errors.len(), if region_errors.len() == 1 { "" } else { "s" });
-                    for ([x, z], e) in region_errors {
-                        println!("{x}, {z}: {e} (debug info: {e:?})");
+                    error!("failed to decode {} region{}:", region_errors.len(), if region_errors.len() == 1 { "" } else { "s" });
+                    for ((dimension, [x, z]), e) in region_errors {
+                        error!("{} {x}, {z}: {e} (debug info: {e:?})", dimension_names[dimension]);
                     }
                 }
                 if !col_errors.is_empty() {
-                    println!("failed to decode {} chunk columns{}:", col_errors.len(), if col_errors.len() == 1 { "" } else { "s" });
-                    for ([x, z], e) in col_errors {
-                        println!("in region {x}, {z}: {e} (debug info: {e:?})");
+                    error!("failed to decode chunk columns in {} region{}:", col_errors.len(), if col_errors.len() == 1 { "" } else { "s" });
+                    for ((dimension, [x, z]), e) in col_errors {
+                        error!("in {} region {x}, {z}: {e} (debug info: {e:?})", dimension_names[dimension]);
                     }
                 }
             }
             _ => {
-                eprintln!("{cmd_name}: {self}");
-                eprintln!("debug info: {self:?}");
+                error!("{cmd_name}: {self}");
+                error!("debug info: {self:?}");
             }
         }
         #[cfg(not(feature = "flamegraph"))] {
@@ -273,210 +901,887 @@ impl wheel::CustomExit for Error {
     }
 }
 
+fn region_dir(world_dir: &Path, dimension: Dimension) -> PathBuf {
+    let dimension_dir = match dimension {
+        Dimension::Overworld => world_dir.to_owned(),
+        Dimension::Nether => world_dir.join("DIM-1"),
+        Dimension::End => world_dir.join("DIM1"),
+    };
+    dimension_dir.join("region")
+}
+
+fn region_path(world_dir: &Path, dimension: Dimension, [x, z]: [i32; 2]) -> PathBuf {
+    region_dir(world_dir, dimension).join(format!("r.{x}.{z}.mca"))
+}
+
+async fn mtime(path: &Path) -> Result<Option<SystemTime>, Error> {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) => Ok(Some(metadata.modified().at(path)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).at(path).map_err(Error::from),
+    }
+}
+
+/// Removes a file left over from a previous run, if there is one.
+fn remove_stale_file(path: &Path) -> Result<(), Error> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).at(path).map_err(Error::from),
+    }
+}
+
+/// Warns about files in a region directory that look like alternative versions of a region file, such as legacy `.mcr` files or leftover `.mca.tmp` files. Only `.mca` files are rendered, so these may mean the map shows outdated terrain.
+async fn warn_stale_region_files(region_dir: &Path, dimension_name: &str) -> Result<(), Error> {
+    let mut candidates = BTreeMap::<[i32; 2], Vec<(String, Option<SystemTime>)>>::default();
+    let mut entries = tokio::fs::read_dir(region_dir).await.at(region_dir)?;
+    while let Some(entry) = entries.next_entry().await.at(region_dir)? {
+        let Ok(name) = entry.file_name().into_string() else { continue };
+        let Some([x, z, ext]) = name.strip_prefix("r.").and_then(|rest| <[&str; 3]>::try_from(rest.splitn(3, '.').collect::<Vec<_>>()).ok()) else { continue };
+        let (Ok(x), Ok(z)) = (x.parse(), z.parse()) else { continue };
+        if ext.is_empty() { continue }
+        candidates.entry([x, z]).or_default().push((ext.to_owned(), mtime(&entry.path()).await?));
+    }
+    for ([x, z], files) in candidates {
+        if files.len() < 2 && files.iter().all(|(ext, _)| ext == "mca") { continue }
+        let mca_mtime = files.iter().find(|(ext, _)| ext == "mca").map(|(_, mtime)| *mtime);
+        let others = files.iter().filter(|(ext, _)| ext != "mca").map(|(ext, _)| format!("r.{x}.{z}.{ext}")).collect::<Vec<_>>().join(", ");
+        match mca_mtime {
+            Some(mca_mtime) => if files.iter().any(|(ext, mtime)| ext != "mca" && *mtime > mca_mtime) {
+                warn!("{dimension_name} region {x}, {z} has files newer than r.{x}.{z}.mca ({others}); only the .mca file is rendered, so the map may show outdated terrain");
+            } else {
+                warn!("{dimension_name} region {x}, {z} has leftover files besides r.{x}.{z}.mca ({others}), which are ignored");
+            },
+            None => warn!("{dimension_name} region {x}, {z} only has files in other formats ({others}) and is not rendered; open the world in a current version of Minecraft to convert it"),
+        }
+    }
+    Ok(())
+}
+
+/// The dimensions rendered into the output directory by this or an earlier run, recognized by their `metadata.json`, so rendering only some dimensions doesn't drop the others from the viewer. Vanilla dimensions come first, in their usual order, followed by datapack dimensions sorted by name.
+async fn rendered_dimensions(out_dir: &Path) -> Result<Vec<String>, Error> {
+    let vanilla = [Dimension::Overworld, Dimension::Nether, Dimension::End].map(dimension_dir_name);
+    let mut dimensions = Vec::default();
+    for name in vanilla {
+        let path = out_dir.join(name).join("metadata.json");
+        if tokio::fs::try_exists(&path).await.at(&path)? {
+            dimensions.push(name.to_owned());
+        }
+    }
+    // datapack dimensions are rendered into `<namespace>/<path>`
+    let mut custom = BTreeSet::default();
+    let mut namespaces = tokio::fs::read_dir(out_dir).await.at(out_dir)?;
+    while let Some(namespace) = namespaces.next_entry().await.at(out_dir)? {
+        let Ok(namespace_name) = namespace.file_name().into_string() else { continue };
+        let namespace_dir = namespace.path();
+        if vanilla.contains(&&*namespace_name) || !namespace.file_type().await.at(&namespace_dir)?.is_dir() { continue }
+        let mut paths = tokio::fs::read_dir(&namespace_dir).await.at(&namespace_dir)?;
+        while let Some(path) = paths.next_entry().await.at(&namespace_dir)? {
+            let Ok(path_name) = path.file_name().into_string() else { continue };
+            let metadata_path = path.path().join("metadata.json");
+            if tokio::fs::try_exists(&metadata_path).await.at(&metadata_path)? {
+                custom.insert(format!("{namespace_name}/{path_name}"));
+            }
+        }
+    }
+    dimensions.extend(custom);
+    Ok(dimensions)
+}
+
+/// Copies the files in a region directory, keeping their modification times so incremental rendering still works. Files that can't be copied, e.g. because they're locked, are skipped with a warning.
+fn copy_region_files(src: &Path, dest: &Path) -> Result<(), Error> {
+    std::fs::create_dir_all(dest).at(dest)?;
+    for entry in std::fs::read_dir(src).at(src)? {
+        let entry = entry.at(src)?;
+        let path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+        let copied = std::fs::copy(&path, &dest_path)
+            .and_then(|_| entry.metadata()?.modified())
+            .and_then(|modified| std::fs::File::options().write(true).open(&dest_path)?.set_modified(modified));
+        if let Err(e) = copied {
+            warn!("failed to copy {}, skipping: {e}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Extracts a zipped world into a temporary directory, which is deleted when the returned [`TempDir`] is dropped.
+///
+/// Backups often wrap the world in a top-level folder, so if the archive doesn't have a `level.dat` at its root but has exactly one directory, that directory is used as the world directory instead.
+fn extract_world(path: &Path) -> Result<(TempDir, PathBuf), Error> {
+    let temp_dir = tempfile::tempdir().at_unknown()?;
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path).at(path)?)?;
+    archive.extract(temp_dir.path())?;
+    let mut world_dir = temp_dir.path().to_owned();
+    if !world_dir.join("level.dat").exists() {
+        let entries = std::fs::read_dir(&world_dir).at(&world_dir)?.collect::<Result<Vec<_>, _>>().at(&world_dir)?;
+        if let [entry] = &*entries && entry.path().is_dir() {
+            world_dir = entry.path();
+        }
+    }
+    Ok((temp_dir, world_dir))
+}
+
+/// Reads a region image written by a previous step, along with its crop offset. Returns `None` if the region has no image.
+fn load_region_img(out_dir: &Path, [x, z]: [i32; 2], format: OutputFormat) -> Result<Option<(RgbaImage, CropOffset)>, Error> {
+    let region_img = match image::open(out_dir.join(format!("r.{x}.{z}.{}", format.extension()))) {
+        Ok(region_img) => RgbaImage::from(region_img),
+        Err(ImageError::IoError(e)) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let crop_offset_path = out_dir.join(format!("r.{x}.{z}.json"));
+    let crop_offset = match std::fs::read(&crop_offset_path) {
+        Ok(buf) => serde_json::from_slice(&buf).map_err(Error::CropOffset)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => CropOffset { x: 0, z: 0 },
+        Err(e) => return Err(e).at(&crop_offset_path).map_err(Error::from),
+    };
+    Ok(Some((region_img, crop_offset)))
+}
+
+/// Combines the region images in `out_dir` into a single world image. Each region is given with its position in the grid of region images, which differs from its coordinates if the map is rotated. Regions without an image stay transparent unless a backdrop is given.
+fn stitch(out_dir: &Path, regions: &[([i32; 2], [i64; 2])], zoom: u32, smooth: bool, format: OutputFormat, encode_options: EncodeOptions, backdrop: Option<Backdrop>) -> Result<(), Error> {
+    let Some(min_x) = regions.iter().map(|(_, [x, _])| *x).min() else { return Ok(()) };
+    let Some(max_x) = regions.iter().map(|(_, [x, _])| *x).max() else { return Ok(()) };
+    let Some(min_z) = regions.iter().map(|(_, [_, z])| *z).min() else { return Ok(()) };
+    let Some(max_z) = regions.iter().map(|(_, [_, z])| *z).max() else { return Ok(()) };
+    let width = (max_x - min_x + 1) as u64;
+    let height = (max_z - min_z + 1) as u64;
+    let too_large = || Error::StitchTooLarge { width, height };
+    let region_px = 16 * 32 / zoom;
+    let width_px = u32::try_from(width * u64::from(region_px)).map_err(|_| too_large())?;
+    let height_px = u32::try_from(height * u64::from(region_px)).map_err(|_| too_large())?;
+    // RgbaImage::new panics if the buffer size overflows
+    usize::try_from(u64::from(width_px) * u64::from(height_px) * 4).ok().filter(|&len| len <= isize::MAX as usize).ok_or_else(too_large)?;
+    let mut world_img = RgbaImage::new(width_px, height_px);
+    for &([x, z], [grid_x, grid_z]) in regions {
+        let Some((region_img, crop_offset)) = load_region_img(out_dir, [x, z], format)? else { continue };
+        image::imageops::replace(&mut world_img, &region_img, (grid_x - min_x) * i64::from(region_px) + i64::from(crop_offset.x), (grid_z - min_z) * i64::from(region_px) + i64::from(crop_offset.z));
+    }
+    if smooth {
+        world_img = smooth_edges(&world_img);
+    }
+    if let Some(backdrop) = backdrop {
+        world_img = backdrop.fill(&world_img);
+    }
+    let path = out_dir.join(format!("world.{}", format.extension()));
+    std::fs::write(&path, format.encode(&world_img, encode_options)?).at(&path)?;
+    Ok(())
+}
+
+/// Packs the region images in `out_dir` into `map.mbtiles`, an SQLite database of map tiles that tile servers and offline map apps can read directly. Regions are given with their grid positions like for [`stitch`].
+///
+/// Each region image becomes one tile at the highest zoom level, placed so that the region at grid position 0, 0 is just southeast of the origin. Each lower zoom level combines 2×2 tiles of the level above into one, down to a single tile at zoom level 0. Tiles that would be fully transparent are left out. Since the map isn't a projection of the Earth, the bounds in the metadata are only meaningful relative to each other.
+fn write_mbtiles(out_dir: &Path, name: &str, regions: &[([i32; 2], [i64; 2])], zoom: u32, format: OutputFormat, encode_options: EncodeOptions, backdrop: Option<Backdrop>) -> Result<(), Error> {
+    let Some(extent) = regions.iter().flat_map(|(_, [x, z])| [-x, x + 1, -z, z + 1]).max() else { return Ok(()) };
+    // grid positions range from -2^(max_level - 1) to 2^(max_level - 1) - 1 along both axes
+    let max_level = (extent as u64).next_power_of_two().ilog2() + 1;
+    let half = 1_i64 << (max_level - 1);
+    let tile_px = 16 * 32 / zoom;
+    let path = out_dir.join("map.mbtiles");
+    // tiles from a previous run might not be overwritten if the set of regions changed
+    remove_stale_file(&path)?;
+    let mut db = rusqlite::Connection::open(&path)?;
+    let tx = db.transaction()?;
+    tx.execute_batch("
+        CREATE TABLE metadata (name TEXT, value TEXT);
+        CREATE TABLE tiles (zoom_level INTEGER, tile_column INTEGER, tile_row INTEGER, tile_data BLOB);
+        CREATE UNIQUE INDEX tile_index ON tiles (zoom_level, tile_column, tile_row);
+    ")?;
+    {
+        let mut insert_tile = tx.prepare("INSERT INTO tiles (zoom_level, tile_column, tile_row, tile_data) VALUES (?1, ?2, ?3, ?4)")?;
+        let mut write_tile = |level: u32, [col, row]: [u64; 2], img: &RgbaImage| -> Result<(), Error> {
+            if img.pixels().all(|pixel| pixel[3] == 0) { return Ok(()) }
+            let data = if let Some(backdrop) = backdrop {
+                format.encode(&backdrop.fill(img), encode_options)?
+            } else {
+                format.encode(img, encode_options)?
+            };
+            // MBTiles numbers rows from the bottom
+            insert_tile.execute(rusqlite::params![level, col, (1 << level) - 1 - row, data])?;
+            Ok(())
+        };
+        let mut level_tiles = HashMap::<[u64; 2], RgbaImage>::default();
+        for &([x, z], [grid_x, grid_z]) in regions {
+            let Some((region_img, crop_offset)) = load_region_img(out_dir, [x, z], format)? else { continue };
+            let mut tile = RgbaImage::new(tile_px, tile_px);
+            image::imageops::replace(&mut tile, &region_img, i64::from(crop_offset.x), i64::from(crop_offset.z));
+            let pos = [(grid_x + half) as u64, (grid_z + half) as u64];
+            write_tile(max_level, pos, &tile)?;
+            level_tiles.insert(pos, tile);
+        }
+        for level in (0..max_level).rev() {
+            let mut parents = HashMap::<[u64; 2], RgbaImage>::default();
+            for ([col, row], tile) in level_tiles {
+                let parent = parents.entry([col / 2, row / 2]).or_insert_with(|| RgbaImage::new(tile_px, tile_px));
+                image::imageops::replace(parent, &downscale(&tile, 2), i64::from((col % 2) as u32 * tile_px / 2), i64::from((row % 2) as u32 * tile_px / 2));
+            }
+            for (&pos, tile) in &parents {
+                write_tile(level, pos, tile)?;
+            }
+            level_tiles = parents;
+        }
+    }
+    // convert the tile extent back to longitude and latitude using the inverse Web Mercator projection
+    let min_x = regions.iter().map(|(_, [x, _])| x + half).min().unwrap_or_default();
+    let max_x = regions.iter().map(|(_, [x, _])| x + half + 1).max().unwrap_or_default();
+    let min_z = regions.iter().map(|(_, [_, z])| z + half).min().unwrap_or_default();
+    let max_z = regions.iter().map(|(_, [_, z])| z + half + 1).max().unwrap_or_default();
+    let tiles = (1_i64 << max_level) as f64;
+    let lon = |col: i64| col as f64 / tiles * 360.0 - 180.0;
+    let lat = |row: i64| (std::f64::consts::PI * (1.0 - 2.0 * row as f64 / tiles)).sinh().atan().to_degrees();
+    for (key, value) in [
+        ("name", name.to_owned()),
+        ("format", format.extension().to_owned()),
+        ("type", "baselayer".to_owned()),
+        ("minzoom", "0".to_owned()),
+        ("maxzoom", max_level.to_string()),
+        ("bounds", format!("{},{},{},{}", lon(min_x), lat(max_z), lon(max_x), lat(min_z))),
+    ] {
+        tx.execute("INSERT INTO metadata (name, value) VALUES (?1, ?2)", rusqlite::params![key, value])?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Runs CPU-bound work on the rayon thread pool, which is also used for rendering the chunk columns of each region in parallel.
+async fn compute<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    let (tx, rx) = oneshot::channel();
+    rayon::spawn(move || {
+        // the receiver is only dropped if the main task is cancelled, in which case the result isn't needed
+        let _ = tx.send(panic::catch_unwind(AssertUnwindSafe(f)));
+    });
+    match rx.await.expect("rayon task dropped without sending a result") {
+        Ok(output) => output,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
 #[wheel::main(max_blocking_threads = 0, custom_exit)]
-async fn main(Args { world_dir, out_dir }: Args) -> Result<(), Error> {
-    let block_colors = Arc::new(colors::get_block_colors());
-    fs::create_dir_all(&out_dir).await?;
+async fn main(Args { world_dir, out_dir, dimension, has_ceiling, heightmap, y_level, max_y, min_y, cave_layers, contours, steep_shading, tint_multipliers, west_shading, hillshade, light_azimuth, light_altitude, lights, slime_chunks, markers, players, banners, structures, debug_unknown, emissive, transparent_blocks, inhabited, block_list, census, dry_run, chunk_status, light_map, map_items, palette, colors, palette_coverage, region_filter, grid, grid_color, zoom, rotate, force, since, format, quality, png_fast, png_small, brightness, gamma, background, checkerboard, crop_to_content, emit_empty, smooth_edges, stitch, mbtiles, viewer, jobs, threads, continue_on_error, copy_first, verbose, quiet, nether_ceiling, nether_cutaway }: Args) -> Result<(), Error> {
+    env_logger::Builder::new()
+        // dependencies only log warnings and errors unless enabled via RUST_LOG
+        .filter_level(log::LevelFilter::Warn)
+        .filter_module(module_path!(), if quiet { log::LevelFilter::Warn } else if verbose { log::LevelFilter::Debug } else { log::LevelFilter::Info })
+        .parse_default_env()
+        .init();
+    let start = Instant::now();
+    let encode_options = EncodeOptions {
+        png_compression: if png_fast { PngCompression::Fast } else if png_small { PngCompression::Small } else { PngCompression::Default },
+        quality,
+    };
+    let backdrop = if checkerboard { Some(Backdrop::Checkerboard) } else { background.map(Backdrop::Color) };
+    if let Some(threads) = threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads.get()).build_global()?;
+    }
+    // kept alive until the end of main so the extracted world isn't deleted while rendering
+    let (_temp_dir, world_dir) = if world_dir.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) {
+        let (temp_dir, world_dir) = compute(move || extract_world(&world_dir)).await?;
+        (Some(temp_dir), world_dir)
+    } else {
+        (None, world_dir)
+    };
+    let mut block_colors = wurstmapberg::get_block_colors(palette);
+    if let Some(colors) = colors {
+        let palette = serde_json::from_slice::<HashMap<String, BlockMapColor>>(&fs::read(colors).await?).map_err(Error::Palette)?;
+        block_colors.extend(palette.into_iter().map(|(name, color)| (parse_block_id(&name), color)));
+    }
+    if palette_coverage {
+        let uncovered = wurstmapberg::uncovered_blocks(&block_colors);
+        for id in &uncovered {
+            println!("minecraft:{id}");
+        }
+        info!("{} vanilla block{} without a color", uncovered.len(), if uncovered.len() == 1 { "" } else { "s" });
+        return Ok(())
+    }
+    let block_colors = Arc::new(block_colors);
+    let level = match Level::read(&world_dir).await {
+        Ok(level) => Some(level),
+        // level.dat is only required for some features
+        Err(e) => if slime_chunks || markers { return Err(e.into()) } else { None },
+    };
+    let slime_chunks = level.as_ref().filter(|_| slime_chunks).map(|level| level.seed);
+    let players = if players { Player::read_all(&world_dir).await? } else { Vec::default() };
     let region_errors = Arc::<Mutex<HashMap<_, _>>>::default();
     let col_errors = Arc::<Mutex<HashMap<_, _>>>::default();
-    let mut coords = HashMap::<_, BTreeSet<_>>::default();
-    let mut coords_stream = pin!(Region::all_coords(&world_dir, DIMENSION));
-    while let Some([x, z]) = coords_stream.try_next().await.map_err(Error::ListRegions)? {
-        coords.entry(x).or_default().insert(z);
-    }
-    let mut renderers = FuturesUnordered::default();
-    for (x, zs) in coords {
-        let block_colors = &block_colors;
-        let region_errors = region_errors.clone();
-        let col_errors = col_errors.clone();
+    let unknown_blocks = Arc::<Mutex<BTreeSet<String>>>::default();
+    // how long each rendered region took, how many chunk columns it has, and how many of those are missing the heightmap, for the summary at the end
+    let region_stats = Arc::<Mutex<Vec<((usize, [i32; 2]), Duration, usize, usize)>>>::default();
+    let jobs = Semaphore::new(match jobs {
+        Some(jobs) => jobs.get(),
+        None => std::thread::available_parallelism().map_or(1, NonZero::get),
+    });
+    let overlays = [(inhabited, Overlay::Inhabited), (chunk_status, Overlay::ChunkStatus)].into_iter().filter_map(|(enabled, overlay)| enabled.then_some(overlay)).chain(light_map.map(Overlay::LightMap)).collect::<Vec<_>>();
+    // regions that were loaded or failed to load, excluding those skipped as unchanged
+    let attempted_regions = AtomicUsize::default();
+    let targets = dimension.targets(&world_dir, has_ceiling);
+    for (dimension_idx, target) in targets.iter().enumerate() {
+        let dimension = target.dimension;
+        let world_dir = &target.world_dir;
+        let region_dir = region_dir(world_dir, dimension);
+        if !tokio::fs::try_exists(&region_dir).await.at(&region_dir)? {
+            warn!("no region folder found at {}; the {} has not been generated in this world", region_dir.display(), target.name);
+            continue
+        }
+        warn_stale_region_files(&region_dir, &target.name).await?;
+        // kept alive until this dimension is done so the copies aren't deleted while rendering
+        let (_copy_dir, world_dir) = if copy_first {
+            let copy_dir = tempfile::tempdir().at_unknown()?;
+            let copy_world_dir = copy_dir.path().to_owned();
+            let copy_region_dir = self::region_dir(&copy_world_dir, dimension);
+            compute(move || copy_region_files(&region_dir, &copy_region_dir)).await?;
+            (Some(copy_dir), copy_world_dir)
+        } else {
+            (None, world_dir.clone())
+        };
         let world_dir = &world_dir;
-        let out_dir = &out_dir;
-        renderers.push(async move {
-            let mut prev = None::<Region>;
-            let mut buf = Vec::default();
-            for z in zs {
-                let region = match Region::find_no_diff(world_dir, DIMENSION, [x, z], buf).await { // this is safe since we're not operating on a live server's world dir; read-during-write mitigation is performed by the wrapper script calling rsync in a loop until no changes are synced
-                    Ok(Some(region)) => region,
-                    Ok(None) => return Err(Error::RegionNotFound),
-                    Err(e) => {
-                        region_errors.lock().insert([x, z], e);
-                        buf = Vec::default();
-                        continue
+        let mut coords = HashMap::<_, BTreeSet<_>>::default();
+        let mut coords_stream = pin!(Region::all_coords(world_dir, dimension));
+        while let Some([x, z]) = coords_stream.try_next().await.map_err(Error::ListRegions)? {
+            if !region_filter.is_empty() && !region_filter.iter().any(|range| range.contains([x, z])) { continue }
+            if let Some(since) = since && mtime(&region_path(world_dir, dimension, [x, z])).await?.is_none_or(|mtime| mtime <= since) { continue }
+            coords.entry(x).or_default().insert(z);
+        }
+        if coords.is_empty() && !region_filter.is_empty() {
+            warn!("no regions in the {} match the --region filter", target.name);
+            continue
+        }
+        if coords.is_empty() && since.is_some() {
+            info!("no regions in the {} were modified since the --since time", target.name);
+            continue
+        }
+        let regions = coords.iter().flat_map(|(&x, zs)| zs.iter().map(move |&z| [x, z])).collect::<Vec<_>>();
+        let progress = if verbose || quiet {
+            ProgressBar::hidden()
+        } else {
+            ProgressBar::new(regions.len() as u64).with_style(ProgressStyle::with_template("{prefix} [{bar:40}] {pos}/{len} regions ({eta} remaining)").expect("valid template")).with_prefix(match &level {
+                Some(level) => format!("{} {}", level.name, target.name),
+                None => target.name.clone(),
+            })
+        };
+        if dry_run {
+            let mut validators = FuturesUnordered::default();
+            for &[x, z] in &regions {
+                let region_errors = region_errors.clone();
+                let col_errors = col_errors.clone();
+                let jobs = &jobs;
+                let attempted_regions = &attempted_regions;
+                validators.push(async move {
+                    let _permit = jobs.acquire().await.expect("semaphore is never closed");
+                    attempted_regions.fetch_add(1, Ordering::Relaxed);
+                    match Region::find_no_diff(world_dir, dimension, [x, z], Vec::default()).await {
+                        Ok(Some(region)) => if let Err(e) = compute(move || wurstmapberg::validate_region(&region)).await {
+                            col_errors.lock().insert((dimension_idx, [x, z]), e);
+                        },
+                        Ok(None) => {}
+                        Err(e) => { region_errors.lock().insert((dimension_idx, [x, z]), e); }
                     }
-                };
-                let block_colors = block_colors.clone();
+                    debug!("region {x}, {z} checked");
+                });
+            }
+            while let Some(()) = validators.next().await {
+                progress.inc(1);
+            }
+            progress.finish();
+            continue
+        }
+        let out_dir = out_dir.join(&target.name);
+        fs::create_dir_all(&out_dir).await?;
+        for overlay in &overlays {
+            fs::create_dir_all(out_dir.join(overlay.dir_name())).await?;
+        }
+        if census {
+            let mut counts = BTreeMap::<String, u64>::default();
+            let mut counters = FuturesUnordered::default();
+            for &[x, z] in &regions {
+                let region_errors = region_errors.clone();
                 let col_errors = col_errors.clone();
-                let out_dir = out_dir.clone();
-                (prev, buf) = tokio::task::spawn_blocking(move || {
-                    println!("processing region {}, {}", region.coords[0], region.coords[1]);
-                    let mut region_img = RgbaImage::new(16 * 32, 16 * 32);
-                    for col in &region {
-                        let col = match col {
-                            Ok(col) => col,
-                            Err(e) => {
-                                col_errors.lock().insert([x, z], e);
-                                println!("region {}, {} failed due to chunk column decode error", region.coords[0], region.coords[1]);
-                                return Ok((Some(region), prev.map(|prev| prev.buf).unwrap_or_default()))
-                            }
-                        };
-                        let heightmap = col.heightmaps.get("WORLD_SURFACE").unwrap_or(FALLBACK_HEIGHTMAP);
-                        for (block_z, row) in heightmap.iter().enumerate() {
-                            for (block_x, max_y) in row.iter().enumerate() {
-                                let mut col_color = MapColor::None;
-                                let mut y = *max_y;
-                                while y >= col.y_pos {
-                                    let chunk_y = y.div_euclid(16) as i8;
-                                    let block_y = y.rem_euclid(16) as usize;
-                                    if let Some(chunk) = col.section_at(chunk_y) {
-                                        let block = &chunk.block_relative([block_x as u8, block_y as u8, block_z as u8]);
-                                        let Some(&color) = block_colors.get(&block.name) else {
-                                            y -= 1;
-                                            continue
-                                        };
-                                        col_color = match color {
-                                            BlockMapColor::Single(color) => color,
-                                            BlockMapColor::Bed { head, foot } => if block.properties.get("part").is_some_and(|part| part == "head") { head } else { foot },
-                                            BlockMapColor::Crops { growing, grown } => if block.properties.get("age").is_some_and(|age| age == "7") { grown } else { growing },
-                                            BlockMapColor::Pillar { top, side } => if block.properties.get("axis").is_some_and(|axis| axis != "y") { side } else { top },
-                                            BlockMapColor::Waterloggable { dry, wet } => if block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") { wet } else { dry },
-                                        };
-                                        if col_color != MapColor::None { break }
-                                    }
-                                    if y == col.y_pos { break }
-                                    y -= 1;
-                                }
-                                let x = col.x_pos * 16 + block_x as i32;
-                                let z = col.z_pos * 16 + block_z as i32;
-                                let tint = match col_color {
-                                    MapColor::None => Tint::Normal,
-                                    MapColor::Water => {
-                                        let water_depth = (col.y_pos..=y).rev().take_while(|y| {
-                                            let chunk_y = y.div_euclid(16) as i8;
-                                            let block_y = y.rem_euclid(16) as usize;
-                                            if let Some(chunk) = col.section_at(chunk_y) {
-                                                let block = &chunk.block_relative([block_x as u8, block_y as u8, block_z as u8]);
-                                                let Some(&color) = block_colors.get(&block.name) else { return false };
-                                                let col_color = match color {
-                                                    BlockMapColor::Single(color) => color,
-                                                    BlockMapColor::Bed { head, foot } => if block.properties.get("part").is_some_and(|part| part == "head") { head } else { foot },
-                                                    BlockMapColor::Crops { growing, grown } => if block.properties.get("age").is_some_and(|age| age == "7") { grown } else { growing },
-                                                    BlockMapColor::Pillar { top, side } => if block.properties.get("axis").is_some_and(|axis| axis != "y") { side } else { top },
-                                                    BlockMapColor::Waterloggable { dry, wet } => if block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") { wet } else { dry },
-                                                };
-                                                col_color == MapColor::Water || block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true")
-                                            } else {
-                                                false
-                                            }
-                                        }).count();
-                                        match water_depth {
-                                            ..=2 => Tint::Light,
-                                            3..=4 => if (block_x + block_z) % 2 == 0 { Tint::Light } else { Tint::Normal },
-                                            5..=6 => Tint::Normal,
-                                            7..=9 => if (block_x + block_z) % 2 == 0 { Tint::Normal } else { Tint::Dark },
-                                            _ => Tint::Dark,
-                                        }
-                                    }
-                                    _ => {
-                                        let north_neighbor = 'north_neighbor: {
-                                            if let Some(block_z) = block_z.checked_sub(1) {
-                                                // same chunk
-                                                (col.y_pos..=heightmap[block_z][block_x]).rev().find(|y| {
-                                                    let chunk_y = y.div_euclid(16) as i8;
-                                                    let block_y = y.rem_euclid(16) as usize;
-                                                    if let Some(chunk) = col.section_at(chunk_y) {
-                                                        let block = &chunk.block_relative([block_x as u8, block_y as u8, block_z as u8]);
-                                                        let Some(&color) = block_colors.get(&block.name) else { return false };
-                                                        let col_color = match color {
-                                                            BlockMapColor::Single(color) => color,
-                                                            BlockMapColor::Bed { head, foot } => if block.properties.get("part").is_some_and(|part| part == "head") { head } else { foot },
-                                                            BlockMapColor::Crops { growing, grown } => if block.properties.get("age").is_some_and(|age| age == "7") { grown } else { growing },
-                                                            BlockMapColor::Pillar { top, side } => if block.properties.get("axis").is_some_and(|axis| axis != "y") { side } else { top },
-                                                            BlockMapColor::Waterloggable { dry, wet } => if block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") { wet } else { dry },
-                                                        };
-                                                        col_color != MapColor::None
-                                                    } else {
-                                                        false
-                                                    }
-                                                })
-                                            } else {
-                                                // different chunk
-                                                let north_region = if col.z_pos.rem_euclid(32) > 0 {
-                                                    // same region
-                                                    &region
-                                                } else if let Some(prev) = &prev {
-                                                    // different region
-                                                    prev
-                                                } else {
-                                                    // not on map
-                                                    break 'north_neighbor None
-                                                };
-                                                let col = match north_region.chunk_column([col.x_pos, col.z_pos - 1]) {
-                                                    Ok(col) => col,
-                                                    Err(e) => {
-                                                        col_errors.lock().insert([x, z], e);
-                                                        println!("region {}, {} failed due to chunk column decode error", region.coords[0], region.coords[1]);
-                                                        return Ok((Some(region), prev.map(|prev| prev.buf).unwrap_or_default()))
-                                                    }
-                                                };
-                                                col.and_then(|col| {
-                                                    let heightmap = col.heightmaps.get("WORLD_SURFACE").unwrap_or_else(|| &FALLBACK_HEIGHTMAP);
-                                                    (col.y_pos..=heightmap[15][block_x]).rev().find(|y| {
-                                                        let chunk_y = y.div_euclid(16) as i8;
-                                                        let block_y = y.rem_euclid(16) as usize;
-                                                        if let Some(chunk) = col.section_at(chunk_y) {
-                                                            let block = &chunk.block_relative([block_x as u8, block_y as u8, 15]);
-                                                            let Some(&color) = block_colors.get(&block.name) else { return false };
-                                                            let col_color = match color {
-                                                                BlockMapColor::Single(color) => color,
-                                                                BlockMapColor::Bed { head, foot } => if block.properties.get("part").is_some_and(|part| part == "head") { head } else { foot },
-                                                                BlockMapColor::Crops { growing, grown } => if block.properties.get("age").is_some_and(|age| age == "7") { grown } else { growing },
-                                                                BlockMapColor::Pillar { top, side } => if block.properties.get("axis").is_some_and(|axis| axis != "y") { side } else { top },
-                                                                BlockMapColor::Waterloggable { dry, wet } => if block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") { wet } else { dry },
-                                                            };
-                                                            col_color != MapColor::None
-                                                        } else {
-                                                            false
-                                                        }
-                                                    })
-                                                })
-                                            }
-                                        }.unwrap_or(y);
-                                        match y.cmp(&north_neighbor) {
-                                            Less => Tint::Dark,
-                                            Equal => Tint::Normal,
-                                            Greater => Tint::Light,
-                                        }
-                                    }
-                                };
-                                region_img[(x.rem_euclid(16 * 32) as u32, z.rem_euclid(16 * 32) as u32)] = col_color.tint(tint);
-                            }
+                let jobs = &jobs;
+                let attempted_regions = &attempted_regions;
+                counters.push(async move {
+                    let _permit = jobs.acquire().await.expect("semaphore is never closed");
+                    attempted_regions.fetch_add(1, Ordering::Relaxed);
+                    let region = match Region::find_no_diff(world_dir, dimension, [x, z], Vec::default()).await {
+                        Ok(Some(region)) => region,
+                        Ok(None) => return Ok::<_, Error>(None),
+                        Err(e) => {
+                            region_errors.lock().insert((dimension_idx, [x, z]), e);
+                            return Ok(None)
                         }
+                    };
+                    match compute(move || wurstmapberg::count_blocks(&region)).await {
+                        Ok(region_counts) => Ok(Some(region_counts)),
+                        Err(e) => {
+                            col_errors.lock().insert((dimension_idx, [x, z]), e);
+                            Ok(None)
+                        }
+                    }
+                });
+            }
+            while let Some(region_counts) = counters.try_next().await? {
+                for (name, count) in region_counts.into_iter().flatten() {
+                    *counts.entry(name).or_default() += count;
+                }
+                progress.inc(1);
+            }
+            progress.finish();
+            let mut counts = counts.into_iter().collect::<Vec<_>>();
+            counts.sort_by(|(name1, count1), (name2, count2)| count2.cmp(count1).then_with(|| name1.cmp(name2)));
+            let mut csv = String::from("block,count\n");
+            for (name, count) in counts {
+                csv.push_str(&format!("{name},{count}\n"));
+            }
+            fs::write(out_dir.join("census.csv"), csv).await?;
+            continue
+        }
+        let renderer = Arc::new(Renderer {
+            block_colors: block_colors.clone(),
+            dimension,
+            heightmap,
+            ceiling: target.ceiling,
+            nether_ceiling,
+            nether_cutaway,
+            y_level,
+            max_y,
+            min_y,
+            cave_layers,
+            contours,
+            steep_shading,
+            tint_multipliers,
+            west_shading,
+            hillshade: hillshade.then_some(Hillshade { azimuth: light_azimuth, altitude: light_altitude }),
+            lights,
+            slime_chunks: slime_chunks.filter(|_| target.vanilla),
+            spawn_marker: level.as_ref().filter(|_| markers && target.vanilla).and_then(|level| level.spawn),
+            world_border: level.as_ref().filter(|_| markers && target.vanilla).and_then(|level| level.border),
+            players: if target.vanilla { players.clone() } else { Vec::default() },
+            banners,
+            structures: structures.clone(),
+            debug_unknown,
+            emissive,
+            transparent_blocks,
+            block_census: block_list,
+            grid: grid.then_some(grid_color),
+        });
+        let data_version = Arc::<Mutex<Option<i32>>>::default();
+        let block_counts = Arc::<Mutex<BTreeMap<[i32; 2], BTreeMap<String, u64>>>>::default();
+        let mut renderers = FuturesUnordered::default();
+        for (x, zs) in coords {
+            let renderer = &renderer;
+            let region_errors = region_errors.clone();
+            let col_errors = col_errors.clone();
+            let unknown_blocks = unknown_blocks.clone();
+            let data_version = data_version.clone();
+            let block_counts = block_counts.clone();
+            let region_stats = region_stats.clone();
+            let out_dir = &out_dir;
+            let progress = &progress;
+            let jobs = &jobs;
+            let attempted_regions = &attempted_regions;
+            let overlays = &overlays;
+            renderers.push(async move {
+                let mut changed = BTreeMap::default();
+                for &z in &zs {
+                    let img_mtime = match mtime(&out_dir.join(format!("r.{x}.{z}.{}", format.extension()))).await? {
+                        Some(img_mtime) => Some(img_mtime),
+                        // empty regions have no image, only a marker file from when they were last rendered
+                        None => mtime(&out_dir.join(format!("r.{x}.{z}.empty"))).await?,
+                    };
+                    changed.insert(z, force || block_list || match (mtime(&region_path(world_dir, dimension, [x, z])).await?, img_mtime) {
+                        (Some(region_mtime), Some(img_mtime)) => region_mtime >= img_mtime,
+                        (_, _) => true,
+                    } || renderer.needs_west_neighbor() && match (mtime(&region_path(world_dir, dimension, [x - 1, z])).await?, img_mtime) {
+                        // the westernmost column of a region is shaded based on its west neighbor, so a change there also requires rerendering
+                        (Some(west_mtime), Some(img_mtime)) => west_mtime >= img_mtime,
+                        (_, _) => false,
+                    });
+                }
+                // the northernmost row of a region is shaded based on its north neighbor, so a change there also requires rerendering
+                let needs_render = |z: i32| changed.get(&z).copied().unwrap_or_default() || changed.get(&(z - 1)).copied().unwrap_or_default();
+                let mut prev = None::<SouthEdge>;
+                let mut buf = Vec::default();
+                let mut write = None::<tokio::task::JoinHandle<Result<(), Error>>>;
+                for &z in &zs {
+                    if !needs_render(z) && !needs_render(z + 1) {
+                        prev = None;
+                        progress.inc(1);
+                        continue
                     }
-                    let path = out_dir.join(format!("r.{}.{}.png", region.coords[0], region.coords[1]));
-                    let changed = match image::open(&path) { //TODO(https://github.com/tokio-rs/tokio/issues/7266) async
-                        Ok(old_img) => RgbaImage::from(old_img) != region_img,
-                        Err(ImageError::IoError(e)) if e.kind() == io::ErrorKind::NotFound => true,
-                        Err(e) => return Err(e.into()),
+                    // held until this region is rendered, so at most --jobs decoded regions and images are in memory at once
+                    let _permit = jobs.acquire().await.expect("semaphore is never closed");
+                    attempted_regions.fetch_add(1, Ordering::Relaxed);
+                    let region = match Region::find_no_diff(world_dir, dimension, [x, z], buf).await { // this is safe since we're not operating on a live server's world dir; read-during-write mitigation is performed by the wrapper script calling rsync in a loop until no changes are synced
+                        Ok(Some(region)) => region,
+                        Ok(None) => {
+                            // deleted since it was listed, which only affects this region
+                            debug!("region {x}, {z} no longer exists");
+                            prev = None;
+                            buf = Vec::default();
+                            progress.inc(1);
+                            continue
+                        }
+                        Err(e) => {
+                            region_errors.lock().insert((dimension_idx, [x, z]), e);
+                            // the south neighbor is shaded as if this region weren't on the map
+                            prev = None;
+                            buf = Vec::default();
+                            progress.inc(1);
+                            continue
+                        }
                     };
-                    if changed {
-                        region_img.save_with_format(path, image::ImageFormat::Png)?; //TODO(https://github.com/tokio-rs/tokio/issues/7266) async
-                        println!("region {}, {} saved", region.coords[0], region.coords[1]);
+                    if !needs_render(z) {
+                        // only needed for shading its south neighbor
+                        let renderer = renderer.clone();
+                        (prev, buf) = compute(move || (renderer.south_edge(&region).ok(), region.buf)).await;
+                        progress.inc(1);
+                        continue
+                    }
+                    if prev.as_ref().is_none_or(|prev| prev.coords != [x, z - 1]) {
+                        // the previous region in this stripe isn't the north neighbor, e.g. because there's a gap or the north neighbor was skipped or filtered out, so it's loaded separately like the west neighbor
+                        prev = if region_errors.lock().contains_key(&(dimension_idx, [x, z - 1])) {
+                            // shaded as if the north neighbor weren't on the map, and its errors have already been reported when it was visited
+                            None
+                        } else if let Some(north) = Region::find_no_diff(world_dir, dimension, [x, z - 1], Vec::default()).await.ok().flatten() {
+                            let renderer = renderer.clone();
+                            compute(move || renderer.south_edge(&north).ok()).await
+                        } else {
+                            None
+                        };
+                    }
+                    // the west neighbor is in a different stripe, which may not have been rendered yet, so it's loaded separately
+                    let west = if renderer.needs_west_neighbor() {
+                        Region::find_no_diff(world_dir, dimension, [x - 1, z], Vec::default()).await.ok().flatten()
                     } else {
-                        println!("region {}, {} unchanged", region.coords[0], region.coords[1]);
+                        None
+                    };
+                    let renderer = renderer.clone();
+                    let col_errors = col_errors.clone();
+                    let unknown_blocks = unknown_blocks.clone();
+                    let data_version = data_version.clone();
+                    let block_counts = block_counts.clone();
+                    let region_stats = region_stats.clone();
+                    let path = out_dir.join(format!("r.{x}.{z}.{}", format.extension()));
+                    let old_img = match tokio::fs::read(&path).await {
+                        Ok(old_img) => Some(old_img),
+                        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+                        Err(e) => return Err(e).at(&path).map_err(Error::from),
+                    };
+                    let crop_offset_path = out_dir.join(format!("r.{x}.{z}.json"));
+                    let empty_marker_path = out_dir.join(format!("r.{x}.{z}.empty"));
+                    let overlay_paths = overlays.iter().map(|&overlay| (overlay, out_dir.join(overlay.dir_name()).join(format!("r.{x}.{z}.{}", format.extension())))).collect::<Vec<_>>();
+                    let (encoded, crop_offset, overlays_encoded);
+                    (prev, buf, encoded, crop_offset, overlays_encoded) = compute({
+                        let path = path.clone();
+                        let crop_offset_path = crop_offset_path.clone();
+                        move || {
+                            debug!("processing region {}, {}", region.coords[0], region.coords[1]);
+                            let render_start = Instant::now();
+                            let west = west.and_then(|west| renderer.east_edge(&west).ok());
+                            let RenderedRegion { img: mut region_img, unknown_blocks: region_unknown_blocks, data_version: region_data_version, south_edge, block_counts: region_block_counts, columns, fallback_columns } = match renderer.render(&region, prev.as_ref(), west.as_ref()) {
+                                Ok(rendered) => rendered,
+                                Err(e) => {
+                                    col_errors.lock().insert((dimension_idx, [x, z]), e);
+                                    debug!("region {}, {} failed due to chunk column error", region.coords[0], region.coords[1]);
+                                    return Ok((None, region.buf, None, None, Vec::default()))
+                                }
+                            };
+                            region_stats.lock().push(((dimension_idx, [x, z]), render_start.elapsed(), columns, fallback_columns));
+                            unknown_blocks.lock().extend(region_unknown_blocks);
+                            if block_list {
+                                block_counts.lock().insert([x, z], region_block_counts);
+                            }
+                            let overlays_encoded = overlay_paths.into_iter().map(|(overlay, path)| {
+                                let mut overlay_img = overlay.render(&renderer, &region)?;
+                                if zoom > 1 {
+                                    overlay_img = downscale(&overlay_img, zoom);
+                                }
+                                overlay_img = rotate.apply(overlay_img);
+                                Ok((path, format.encode(&overlay_img, encode_options)?))
+                            }).collect::<Result<Vec<_>, Error>>()?;
+                            {
+                                let mut data_version = data_version.lock();
+                                *data_version = (*data_version).max(region_data_version);
+                            }
+                            if !emit_empty && region_img.pixels().all(|pixel| pixel[3] == 0) {
+                                // remove any output from a previous run where this region wasn't empty
+                                remove_stale_file(&path)?;
+                                remove_stale_file(&crop_offset_path)?;
+                                // lets the next run tell that this region is up to date
+                                std::fs::write(&empty_marker_path, "").at(&empty_marker_path)?;
+                                debug!("region {}, {} is empty", region.coords[0], region.coords[1]);
+                                return Ok((Some(south_edge), region.buf, None, None, overlays_encoded))
+                            }
+                            remove_stale_file(&empty_marker_path)?;
+                            if zoom > 1 {
+                                region_img = downscale(&region_img, zoom);
+                                if smooth_edges {
+                                    region_img = self::smooth_edges(&region_img);
+                                }
+                            }
+                            region_img = rotate.apply(region_img);
+                            let crop_offset = if crop_to_content {
+                                // empty regions are only written with --emit-empty, in which case they're not cropped
+                                let [left, top, width, height] = content_bounds(&region_img).unwrap_or([0, 0, region_img.width(), region_img.height()]);
+                                region_img = image::imageops::crop_imm(&region_img, left, top, width, height).to_image();
+                                Some(CropOffset { x: left, z: top })
+                            } else {
+                                // a crop offset left over from a previous run with --crop-to-content would misplace the uncropped image when stitching
+                                remove_stale_file(&crop_offset_path)?;
+                                None
+                            };
+                            if brightness != 1.0 || gamma != 1.0 {
+                                adjust_colors(&mut region_img, brightness, gamma);
+                            }
+                            if let Some(backdrop) = backdrop {
+                                region_img = backdrop.fill(&region_img);
+                            }
+                            let changed = match old_img {
+                                Some(old_img) if format.is_lossless() => RgbaImage::from(image::load_from_memory_with_format(&old_img, format.image_format())?) != region_img,
+                                // lossy formats can't be compared pixel by pixel
+                                _ => true,
+                            };
+                            let encoded = if changed {
+                                Some(format.encode(&region_img, encode_options)?)
+                            } else {
+                                // update the modification time so this region isn't considered out of date on the next run
+                                std::fs::File::options().write(true).open(&path).and_then(|file| file.set_modified(SystemTime::now())).at(&path)?;
+                                debug!("region {}, {} unchanged", region.coords[0], region.coords[1]);
+                                None
+                            };
+                            Ok::<_, Error>((Some(south_edge), region.buf, encoded, crop_offset, overlays_encoded))
+                        }
+                    }).await?;
+                    progress.inc(1);
+                    if encoded.is_some() || crop_offset.is_some() || !overlays_encoded.is_empty() {
+                        // write in the background so the next region can start rendering, but wait for the previous write to keep memory usage bounded
+                        if let Some(write) = write.take() {
+                            write.await??;
+                        }
+                        write = Some(tokio::spawn(async move {
+                            if let Some(encoded) = encoded {
+                                fs::write(&path, encoded).await?;
+                                debug!("region {x}, {z} saved");
+                            }
+                            if let Some(crop_offset) = crop_offset {
+                                // the offset can change even if the cropped image doesn't
+                                fs::write(&crop_offset_path, serde_json::to_vec(&crop_offset).map_err(Error::CropOffset)?).await?;
+                            }
+                            for (overlay_path, overlay_encoded) in overlays_encoded {
+                                fs::write(overlay_path, overlay_encoded).await?;
+                            }
+                            Ok(())
+                        }));
                     }
-                    Ok::<_, Error>((Some(region), prev.map(|prev| prev.buf).unwrap_or_default()))
-                }).await??;
+                }
+                if let Some(write) = write {
+                    write.await??;
+                }
+                Ok(())
+            });
+        }
+        while let Some(()) = renderers.try_next().await? {}
+        progress.finish();
+        if let (Some(min_x), Some(max_x), Some(min_z), Some(max_z)) = (
+            regions.iter().map(|[x, _]| *x).min(),
+            regions.iter().map(|[x, _]| *x).max(),
+            regions.iter().map(|[_, z]| *z).min(),
+            regions.iter().map(|[_, z]| *z).max(),
+        ) {
+            let metadata = Metadata {
+                dimension: target.name.clone(),
+                regions: [[min_x, min_z], [max_x, max_z]],
+                blocks: [[i64::from(min_x) * 16 * 32, i64::from(min_z) * 16 * 32], [(i64::from(max_x) + 1) * 16 * 32 - 1, (i64::from(max_z) + 1) * 16 * 32 - 1]],
+                pixels: {
+                    let pixels = [(i64::from(max_x) - i64::from(min_x) + 1) as u64 * u64::from(16 * 32 / zoom), (i64::from(max_z) - i64::from(min_z) + 1) as u64 * u64::from(16 * 32 / zoom)];
+                    if matches!(rotate, Rotation::Quarter | Rotation::ThreeQuarters) { [pixels[1], pixels[0]] } else { pixels }
+                },
+                zoom,
+                rotation: rotate.degrees(),
+                data_version: *data_version.lock(),
+                rendered_at: Local::now().to_rfc3339(),
+            };
+            fs::write(out_dir.join("metadata.json"), serde_json::to_vec_pretty(&metadata).map_err(Error::Metadata)?).await?;
+        }
+        if block_list {
+            let mut csv = String::from("region_x,region_z,block,count\n");
+            for ([x, z], counts) in &*block_counts.lock() {
+                for (name, count) in counts {
+                    csv.push_str(&format!("{x},{z},{name},{count}\n"));
+                }
             }
-            Ok(())
-        });
+            fs::write(out_dir.join("blocks.csv"), csv).await?;
+        }
+        if stitch {
+            let out_dir = out_dir.clone();
+            let regions = regions.iter().map(|&coords| (coords, rotate.region(coords))).collect::<Vec<_>>();
+            compute(move || self::stitch(&out_dir, &regions, zoom, smooth_edges, format, encode_options, backdrop)).await?;
+        }
+        if mbtiles {
+            let out_dir = out_dir.clone();
+            let name = target.name.clone();
+            let regions = regions.iter().map(|&coords| (coords, rotate.region(coords))).collect::<Vec<_>>();
+            compute(move || write_mbtiles(&out_dir, &name, &regions, zoom, format, encode_options, backdrop)).await?;
+        }
     }
-    while let Some(()) = renderers.try_next().await? {}
-    let region_errors = Arc::into_inner(region_errors).unwrap().into_inner();
-    let col_errors = Arc::into_inner(col_errors).unwrap().into_inner();
-    if !region_errors.is_empty() || !col_errors.is_empty() {
-        Err(Error::RegionsCols { region_errors, col_errors })
-    } else {
-        println!("all regions rendered successfully");
+    if map_items && !dry_run {
+        let maps_dir = out_dir.join("maps");
+        fs::create_dir_all(&maps_dir).await?;
+        for map in MapItem::read_all(&world_dir).await? {
+            // map items are only 128×128 pixels, so this isn't worth moving to the thread pool
+            fs::write(maps_dir.join(format!("map_{}.png", map.id)), OutputFormat::Png.encode(&map.render(), encode_options)?).await?;
+        }
+    }
+    if viewer && !dry_run {
+        fs::create_dir_all(&out_dir).await?;
+        let config = ViewerConfig { dimensions: rendered_dimensions(&out_dir).await?, extension: format.extension(), stitched: stitch, cropped: crop_to_content };
+        let html = include_str!("../assets/viewer.html").replace("/*CONFIG*/", &serde_json::to_string(&config).map_err(Error::Viewer)?);
+        fs::write(out_dir.join("index.html"), html).await?;
+    }
+    let dimension_names = targets.into_iter().map(|target| target.name).collect::<Vec<_>>();
+    let region_errors = sorted_errors(Arc::into_inner(region_errors).unwrap().into_inner());
+    let col_errors = sorted_errors(Arc::into_inner(col_errors).unwrap().into_inner());
+    let unknown_blocks = Arc::into_inner(unknown_blocks).unwrap().into_inner();
+    if !unknown_blocks.is_empty() {
+        warn!("{} block{} missing from the color table: {}", unknown_blocks.len(), if unknown_blocks.len() == 1 { " is" } else { "s are" }, unknown_blocks.into_iter().collect::<Vec<_>>().join(", "));
+    }
+    let mut region_stats = Arc::into_inner(region_stats).unwrap().into_inner();
+    if !region_stats.is_empty() {
+        let render_time = region_stats.iter().map(|(_, duration, _, _)| *duration).sum::<Duration>();
+        let total_columns = region_stats.iter().map(|(_, _, columns, _)| columns).sum::<usize>();
+        info!(
+            "rendered {} region{} ({} chunk columns) in {:.1?}, {:.1?} per region on average",
+            region_stats.len(),
+            if region_stats.len() == 1 { "" } else { "s" },
+            total_columns,
+            start.elapsed(),
+            render_time / region_stats.len() as u32,
+        );
+        let fallback_columns = region_stats.iter().map(|(_, _, _, fallback_columns)| fallback_columns).sum::<usize>();
+        if fallback_columns > 0 {
+            warn!("{fallback_columns} of {total_columns} chunk columns ({:.1}%) are missing the heightmap selected with --heightmap, so it was recomputed from the block data", fallback_columns as f64 * 100.0 / total_columns as f64);
+            if fallback_columns * 2 > total_columns {
+                warn!("this slows down rendering, and the recomputed motion blocking heightmaps are approximate; loading the world in Minecraft or optimizing it will store the heightmaps");
+            }
+        }
+        if log::log_enabled!(log::Level::Debug) {
+            region_stats.sort_by(|(_, duration1, _, _), (_, duration2, _, _)| duration2.cmp(duration1));
+            debug!("slowest regions:");
+            for ((dimension, [x, z]), duration, columns, _) in region_stats.into_iter().take(5) {
+                debug!("{} {x}, {z}: {duration:.1?} ({columns} chunk columns)", dimension_names[dimension]);
+            }
+        }
+    }
+    if region_errors.is_empty() && col_errors.is_empty() {
+        info!("all regions {} successfully", if dry_run { "decoded" } else { "rendered" });
+        Ok(())
+    } else if continue_on_error && region_errors.len() + col_errors.len() < attempted_regions.into_inner() {
+        warn!("{}", format_region_col_errors(&dimension_names, &region_errors, &col_errors));
         Ok(())
+    } else {
+        Err(Error::RegionsCols { dimension_names, region_errors, col_errors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_namespace() {
+        assert_eq!(parse_block_id("stone"), BlockId::Stone);
+        assert_eq!(parse_block_id("minecraft:stone"), BlockId::Stone);
+        assert_ne!(parse_block_id("example:stone"), BlockId::Stone);
+        assert_eq!(parse_block_id("foo").to_string(), "minecraft:foo");
+        assert_eq!(parse_block_id("example:foo").to_string(), "example:foo");
+    }
+
+    #[test]
+    fn downscale_keeps_sparse_squares() {
+        let mut img = RgbaImage::new(32, 16);
+        img[(3, 5)] = Rgba([255, 0, 0, u8::MAX]);
+        let downscaled = downscale(&img, 16);
+        assert_eq!(downscaled[(0, 0)], Rgba([255, 0, 0, 1]));
+        // only squares that are entirely transparent stay transparent
+        assert_eq!(downscaled[(1, 0)], Rgba([0; 4]));
+    }
+
+    #[test]
+    fn negative_region_range() {
+        let range = "-1,-2".parse::<RegionRange>().unwrap();
+        assert_eq!((range.min, range.max), ([-1, -2], [-1, -2]));
+        let range = "-1,-3..-3,-1".parse::<RegionRange>().unwrap();
+        assert_eq!((range.min, range.max), ([-3, -3], [-1, -1]));
+        assert!(range.contains([-2, -2]));
+        assert!(!range.contains([0, -2]));
+        assert!(!range.contains([-2, 0]));
+        assert!("-1".parse::<RegionRange>().is_err());
+    }
+
+    #[test]
+    fn rotate_negative_region() {
+        assert_eq!(Rotation::None.region([-1, -1]), [-1, -1]);
+        assert_eq!(Rotation::Quarter.region([-1, -1]), [0, -1]);
+        assert_eq!(Rotation::Half.region([-1, -1]), [0, 0]);
+        assert_eq!(Rotation::ThreeQuarters.region([-1, -1]), [-1, 0]);
+    }
+
+    #[test]
+    fn rendered_dimensions_from_earlier_runs() {
+        let out_dir = tempfile::tempdir().unwrap();
+        for dimension in ["end", "overworld", "example/moon"] {
+            std::fs::create_dir_all(out_dir.path().join(dimension)).unwrap();
+            std::fs::write(out_dir.path().join(dimension).join("metadata.json"), "{}").unwrap();
+        }
+        // a dimension without metadata.json, e.g. from an interrupted run, isn't shown
+        std::fs::create_dir_all(out_dir.path().join("nether")).unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        assert_eq!(runtime.block_on(rendered_dimensions(out_dir.path())).unwrap(), ["overworld", "end", "example/moon"]);
+    }
+
+    #[test]
+    fn stitch_negative_regions() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let encode_options = EncodeOptions { quality: 90, png_compression: PngCompression::Default };
+        // at the highest zoom level, each region is a single pixel
+        let colors = [([-1, -1], Rgba([255, 0, 0, u8::MAX])), ([0, -1], Rgba([0, 255, 0, u8::MAX])), ([-1, 0], Rgba([0, 0, 255, u8::MAX]))];
+        for ([x, z], color) in colors {
+            std::fs::write(out_dir.path().join(format!("r.{x}.{z}.png")), OutputFormat::Png.encode(&RgbaImage::from_pixel(1, 1, color), encode_options).unwrap()).unwrap();
+        }
+        let regions = colors.map(|(coords, _)| (coords, Rotation::None.region(coords)));
+        stitch(out_dir.path(), &regions, 16 * 32, false, OutputFormat::Png, encode_options, None).unwrap();
+        let world_img = RgbaImage::from(image::open(out_dir.path().join("world.png")).unwrap());
+        assert_eq!(world_img.dimensions(), (2, 2));
+        for ([x, z], color) in colors {
+            assert_eq!(*world_img.get_pixel((x + 1) as u32, (z + 1) as u32), color);
+        }
+        assert_eq!(*world_img.get_pixel(1, 1), Rgba([0; 4]));
+    }
+
+    #[test]
+    fn mbtiles_pyramid() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let encode_options = EncodeOptions { quality: 90, png_compression: PngCompression::Default };
+        // at zoom 256, each region is 2×2 pixels, so the single tile at zoom level 0 still has one pixel per region
+        let colors = [([-1, -1], Rgba([255, 0, 0, u8::MAX])), ([0, 0], Rgba([0, 255, 0, u8::MAX]))];
+        for ([x, z], color) in colors {
+            std::fs::write(out_dir.path().join(format!("r.{x}.{z}.png")), OutputFormat::Png.encode(&RgbaImage::from_pixel(2, 2, color), encode_options).unwrap()).unwrap();
+        }
+        let regions = colors.map(|(coords, _)| (coords, Rotation::None.region(coords)));
+        write_mbtiles(out_dir.path(), "overworld", &regions, 16 * 16, OutputFormat::Png, encode_options, None).unwrap();
+        let db = rusqlite::Connection::open(out_dir.path().join("map.mbtiles")).unwrap();
+        let max_zoom = db.query_row("SELECT value FROM metadata WHERE name = 'maxzoom'", [], |row| row.get::<_, String>(0)).unwrap();
+        assert_eq!(max_zoom, "1");
+        let tile = |level: u32, col: u32, tile_row: u32| {
+            let data = db.query_row("SELECT tile_data FROM tiles WHERE zoom_level = ?1 AND tile_column = ?2 AND tile_row = ?3", rusqlite::params![level, col, tile_row], |row| row.get::<_, Vec<u8>>(0)).unwrap();
+            RgbaImage::from(image::load_from_memory(&data).unwrap())
+        };
+        // rows are numbered from the bottom, so the northwestern region is in the top row
+        assert_eq!(tile(1, 0, 1), RgbaImage::from_pixel(2, 2, colors[0].1));
+        assert_eq!(tile(1, 1, 0), RgbaImage::from_pixel(2, 2, colors[1].1));
+        let overview = tile(0, 0, 0);
+        assert_eq!(*overview.get_pixel(0, 0), colors[0].1);
+        assert_eq!(*overview.get_pixel(1, 1), colors[1].1);
+        assert_eq!(*overview.get_pixel(1, 0), Rgba([0; 4]));
+        // empty tiles are left out
+        assert_eq!(db.query_row("SELECT COUNT(*) FROM tiles", [], |row| row.get::<_, u32>(0)).unwrap(), 3);
     }
 }
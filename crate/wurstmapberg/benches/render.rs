@@ -0,0 +1,59 @@
+#![allow(unused_crate_dependencies)] // dependencies of the library are also available to its benchmarks
+
+use {
+    std::{
+        hint::black_box,
+        path::Path,
+        sync::Arc,
+    },
+    criterion::{
+        Criterion,
+        criterion_group,
+        criterion_main,
+    },
+    mcanvil::{
+        Dimension,
+        Region,
+    },
+    rayon::prelude::*,
+    wurstmapberg::{
+        Palette,
+        Renderer,
+        get_block_colors,
+    },
+};
+
+/// Loads the fixture region described in `tests/fixtures/README.md`. Decoding isn't part of the measurements.
+fn fixture_region() -> Region {
+    let world_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join("world");
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    runtime.block_on(Region::find_no_diff(&world_dir, Dimension::Overworld, [0, 0], Vec::default())).unwrap().unwrap()
+}
+
+/// The column scan, water depth, and neighbor shading of an entire region.
+fn render(c: &mut Criterion) {
+    let renderer = Renderer::new(Dimension::Overworld, Arc::new(get_block_colors(Palette::Vanilla)));
+    let region = fixture_region();
+    c.bench_function("render", |b| b.iter(|| renderer.render(black_box(&region), None, None).unwrap()));
+    c.bench_function("south_edge", |b| b.iter(|| renderer.south_edge(black_box(&region)).unwrap()));
+}
+
+/// Renders several regions at once like the CLI does, either on tokio's blocking threads, each of which waits for the chunk columns to be rendered on the rayon pool, or entirely on the rayon pool.
+fn concurrent(c: &mut Criterion) {
+    const REGIONS: usize = 8;
+
+    let renderer = Arc::new(Renderer::new(Dimension::Overworld, Arc::new(get_block_colors(Palette::Vanilla))));
+    let region = Arc::new(fixture_region());
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let mut group = c.benchmark_group("concurrent");
+    group.bench_function("spawn_blocking", |b| b.iter(|| runtime.block_on(futures::future::try_join_all((0..REGIONS).map(|_| {
+        let renderer = Arc::clone(&renderer);
+        let region = Arc::clone(&region);
+        tokio::task::spawn_blocking(move || renderer.render(&region, None, None).unwrap())
+    }))).unwrap()));
+    group.bench_function("rayon", |b| b.iter(|| (0..REGIONS).into_par_iter().map(|_| renderer.render(&region, None, None).unwrap()).collect::<Vec<_>>()));
+    group.finish();
+}
+
+criterion_group!(benches, render, concurrent);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum BiomeColorKind {
+    Grass,
+    Foliage,
+    Water,
+}
+
+impl BiomeColorKind {
+    /// The biome color the map color table is calibrated against (that of the plains biome).
+    fn reference(&self) -> u32 {
+        match self {
+            Self::Grass => 0x91bd59,
+            Self::Foliage => 0x77ab2f,
+            Self::Water => 0x3f76e4,
+        }
+    }
+
+    /// Based on the biome colors listed at <https://minecraft.wiki/w/Color#Biome_colors>. Biomes not listed here use the reference color.
+    fn color(&self, biome: &str) -> u32 {
+        match (self, biome.strip_prefix("minecraft:").unwrap_or(biome)) {
+            (Self::Grass, "badlands" | "eroded_badlands" | "wooded_badlands") => 0x90814d,
+            (Self::Grass, "bamboo_jungle" | "jungle") => 0x59c93c,
+            (Self::Grass, "birch_forest" | "old_growth_birch_forest") => 0x88bb67,
+            (Self::Grass, "cherry_grove") => 0xb6db61,
+            (Self::Grass, "dark_forest") => 0x507a32,
+            (Self::Grass, "desert" | "savanna" | "savanna_plateau" | "windswept_savanna" | "nether_wastes" | "soul_sand_valley" | "crimson_forest" | "warped_forest" | "basalt_deltas") => 0xbfb755,
+            (Self::Grass, "flower_forest" | "forest") => 0x79c05a,
+            (Self::Grass, "frozen_peaks" | "frozen_river" | "grove" | "ice_spikes" | "jagged_peaks" | "snowy_beach" | "snowy_plains" | "snowy_slopes" | "snowy_taiga") => 0x80b497,
+            (Self::Grass, "mangrove_swamp" | "swamp") => 0x6a7039,
+            (Self::Grass, "meadow") => 0x83bb6d,
+            (Self::Grass, "mushroom_fields") => 0x55c93f,
+            (Self::Grass, "old_growth_pine_taiga") => 0x86b87f,
+            (Self::Grass, "old_growth_spruce_taiga" | "taiga") => 0x86b783,
+            (Self::Grass, "pale_garden") => 0x778272,
+            (Self::Grass, "sparse_jungle") => 0x64c73f,
+            (Self::Grass, "stony_peaks") => 0x9abe4b,
+            (Self::Grass, "stony_shore" | "windswept_forest" | "windswept_gravelly_hills" | "windswept_hills") => 0x8ab689,
+            (Self::Foliage, "badlands" | "eroded_badlands" | "wooded_badlands") => 0x9e814d,
+            (Self::Foliage, "bamboo_jungle" | "jungle") => 0x30bb0b,
+            (Self::Foliage, "birch_forest" | "old_growth_birch_forest") => 0x6ba941,
+            (Self::Foliage, "cherry_grove") => 0xb6db61,
+            (Self::Foliage, "dark_forest" | "flower_forest" | "forest") => 0x59ae30,
+            (Self::Foliage, "desert" | "savanna" | "savanna_plateau" | "windswept_savanna" | "nether_wastes" | "soul_sand_valley" | "crimson_forest" | "warped_forest" | "basalt_deltas") => 0xaea42a,
+            (Self::Foliage, "frozen_peaks" | "frozen_river" | "grove" | "ice_spikes" | "jagged_peaks" | "snowy_beach" | "snowy_plains" | "snowy_slopes" | "snowy_taiga") => 0x60a17b,
+            (Self::Foliage, "mangrove_swamp") => 0x8db127,
+            (Self::Foliage, "swamp") => 0x6a7039,
+            (Self::Foliage, "meadow") => 0x63a948,
+            (Self::Foliage, "mushroom_fields") => 0x2bbb0f,
+            (Self::Foliage, "old_growth_pine_taiga") => 0x68a55f,
+            (Self::Foliage, "old_growth_spruce_taiga" | "taiga") => 0x68a464,
+            (Self::Foliage, "pale_garden") => 0x878d76,
+            (Self::Foliage, "sparse_jungle") => 0x3eb80f,
+            (Self::Foliage, "stony_peaks") => 0x82ac1e,
+            (Self::Foliage, "stony_shore" | "windswept_forest" | "windswept_gravelly_hills" | "windswept_hills") => 0x6da36b,
+            (Self::Water, "cherry_grove") => 0x5db7ef,
+            (Self::Water, "cold_ocean" | "deep_cold_ocean" | "snowy_taiga" | "snowy_beach") => 0x3d57d6,
+            (Self::Water, "deep_frozen_ocean" | "frozen_ocean" | "frozen_river") => 0x3938c9,
+            (Self::Water, "deep_lukewarm_ocean" | "lukewarm_ocean") => 0x45adf2,
+            (Self::Water, "mangrove_swamp") => 0x3a7a6a,
+            (Self::Water, "meadow") => 0x0e4ecf,
+            (Self::Water, "pale_garden") => 0x76889d,
+            (Self::Water, "swamp") => 0x617b64,
+            (Self::Water, "warm_ocean") => 0x43d5ee,
+            (_, _) => self.reference(),
+        }
+    }
+
+    /// Scales each channel of the given map color by how much the biome color differs from the reference color.
+    pub(crate) fn apply(&self, base_rgb: u32, biome: &str) -> u32 {
+        let [_, r, g, b] = base_rgb.to_be_bytes();
+        let [_, biome_r, biome_g, biome_b] = self.color(biome).to_be_bytes();
+        let [_, ref_r, ref_g, ref_b] = self.reference().to_be_bytes();
+        let [r, g, b] = [(r, biome_r, ref_r), (g, biome_g, ref_g), (b, biome_b, ref_b)].map(|(channel, biome, reference)| {
+            (u32::from(channel) * u32::from(biome) / u32::from(reference).max(1)).min(u8::MAX.into()) as u8
+        });
+        u32::from_be_bytes([0, r, g, b])
+    }
+}
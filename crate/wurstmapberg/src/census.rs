@@ -0,0 +1,27 @@
+use {
+    std::collections::BTreeMap,
+    mcanvil::Region,
+    crate::Error,
+};
+
+/// Counts every block in every section of every chunk column of the region. Unlike [`Renderer::block_census`](crate::Renderer::block_census), this includes everything below the surface, so it's much slower than rendering.
+pub fn count_blocks(region: &Region) -> Result<BTreeMap<String, u64>, Error> {
+    let [region_x, region_z] = region.coords;
+    let mut counts = BTreeMap::<_, u64>::default();
+    for chunk_z in 0..32 {
+        for chunk_x in 0..32 {
+            let Some(col) = region.chunk_column([region_x * 32 + chunk_x, region_z * 32 + chunk_z])? else { continue };
+            for chunk_y in col.y_pos.div_euclid(16)..=i32::from(i8::MAX) {
+                let Some(chunk) = i8::try_from(chunk_y).ok().and_then(|chunk_y| col.section_at(chunk_y)) else { continue };
+                for block_y in 0..16 {
+                    for block_z in 0..16 {
+                        for block_x in 0..16 {
+                            *counts.entry(chunk.block_relative([block_x, block_y, block_z]).name.to_string()).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(counts)
+}
@@ -5,26 +5,27 @@ use {
     crate::{
         BlockMapColor::*,
         MapColor::*,
+        biome::BiomeColorKind,
     },
 };
 
 /// Up to date as of Minecraft 26.1.2
-pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
+pub(crate) fn vanilla_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
     collect![
         BlockId::AcaciaButton => Single(None),
         BlockId::AcaciaDoor => Single(ColorOrange),
         BlockId::AcaciaFence => Single(ColorOrange),
         BlockId::AcaciaFenceGate => Single(ColorOrange),
         BlockId::AcaciaHangingSign => Single(ColorOrange),
-        BlockId::AcaciaLeaves => Single(Plant),
+        BlockId::AcaciaLeaves => Biome { color: Plant, kind: BiomeColorKind::Foliage },
         BlockId::AcaciaLog => Pillar { top: ColorOrange, side: Stone },
         BlockId::AcaciaPlanks => Single(ColorOrange),
-        BlockId::AcaciaPressurePlate => Single(ColorOrange),
+        BlockId::AcaciaPressurePlate => Thin { color: ColorOrange },
         BlockId::AcaciaSapling => Single(Plant),
         BlockId::AcaciaShelf => Single(ColorOrange),
         BlockId::AcaciaSign => Single(ColorOrange),
-        BlockId::AcaciaSlab => Single(ColorOrange),
-        BlockId::AcaciaStairs => Single(ColorOrange),
+        BlockId::AcaciaSlab => Slab { color: ColorOrange },
+        BlockId::AcaciaStairs => Stairs { color: ColorOrange },
         BlockId::AcaciaTrapdoor => Single(ColorOrange),
         BlockId::AcaciaWallHangingSign => Single(ColorOrange),
         BlockId::AcaciaWallSign => Single(ColorOrange),
@@ -36,8 +37,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::AmethystCluster => Single(ColorPurple),
         BlockId::AncientDebris => Single(ColorBlack),
         BlockId::Andesite => Single(Stone),
-        BlockId::AndesiteSlab => Single(Stone),
-        BlockId::AndesiteStairs => Single(Stone),
+        BlockId::AndesiteSlab => Slab { color: Stone },
+        BlockId::AndesiteStairs => Stairs { color: Stone },
         BlockId::AndesiteWall => Single(Stone),
         BlockId::Anvil => Single(Metal),
         BlockId::AttachedMelonStem => Single(Plant),
@@ -53,15 +54,15 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::BambooFenceGate => Single(ColorYellow),
         BlockId::BambooHangingSign => Single(ColorYellow),
         BlockId::BambooMosaic => Single(ColorYellow),
-        BlockId::BambooMosaicSlab => Single(ColorYellow),
-        BlockId::BambooMosaicStairs => Single(ColorYellow),
+        BlockId::BambooMosaicSlab => Slab { color: ColorYellow },
+        BlockId::BambooMosaicStairs => Stairs { color: ColorYellow },
         BlockId::BambooPlanks => Single(ColorYellow),
-        BlockId::BambooPressurePlate => Single(ColorYellow),
+        BlockId::BambooPressurePlate => Thin { color: ColorYellow },
         BlockId::BambooSapling => Single(Wood),
         BlockId::BambooShelf => Single(ColorYellow),
         BlockId::BambooSign => Single(ColorYellow),
-        BlockId::BambooSlab => Single(ColorYellow),
-        BlockId::BambooStairs => Single(ColorYellow),
+        BlockId::BambooSlab => Slab { color: ColorYellow },
+        BlockId::BambooStairs => Stairs { color: ColorYellow },
         BlockId::BambooTrapdoor => Single(ColorYellow),
         BlockId::BambooWallHangingSign => Single(ColorYellow),
         BlockId::BambooWallSign => Single(ColorYellow),
@@ -84,12 +85,12 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::BirchLeaves => Single(Plant),
         BlockId::BirchLog => Pillar { top: Sand, side: Quartz },
         BlockId::BirchPlanks => Single(Sand),
-        BlockId::BirchPressurePlate => Single(Sand),
+        BlockId::BirchPressurePlate => Thin { color: Sand },
         BlockId::BirchSapling => Single(Plant),
         BlockId::BirchShelf => Single(Sand),
         BlockId::BirchSign => Single(Sand),
-        BlockId::BirchSlab => Single(Sand),
-        BlockId::BirchStairs => Single(Sand),
+        BlockId::BirchSlab => Slab { color: Sand },
+        BlockId::BirchStairs => Stairs { color: Sand },
         BlockId::BirchTrapdoor => Single(Sand),
         BlockId::BirchWallHangingSign => Single(Sand),
         BlockId::BirchWallSign => Single(Sand),
@@ -98,7 +99,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::BlackBed => Bed { head: Wool, foot: ColorBlack },
         BlockId::BlackCandle => Single(ColorBlack),
         BlockId::BlackCandleCake => Single(None),
-        BlockId::BlackCarpet => Single(ColorBlack),
+        BlockId::BlackCarpet => Thin { color: ColorBlack },
         BlockId::BlackConcrete => Single(ColorBlack),
         BlockId::BlackConcretePowder => Single(ColorBlack),
         BlockId::BlackGlazedTerracotta => Single(ColorBlack),
@@ -109,15 +110,15 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::BlackWallBanner => Single(Wood),
         BlockId::BlackWool => Single(ColorBlack),
         BlockId::Blackstone => Single(ColorBlack),
-        BlockId::BlackstoneSlab => Single(ColorBlack),
-        BlockId::BlackstoneStairs => Single(ColorBlack),
+        BlockId::BlackstoneSlab => Slab { color: ColorBlack },
+        BlockId::BlackstoneStairs => Stairs { color: ColorBlack },
         BlockId::BlackstoneWall => Single(ColorBlack),
         BlockId::BlastFurnace => Single(Stone),
         BlockId::BlueBanner => Single(Wood),
         BlockId::BlueBed => Bed { head: Wool, foot: ColorBlue },
         BlockId::BlueCandle => Single(ColorBlue),
         BlockId::BlueCandleCake => Single(None),
-        BlockId::BlueCarpet => Single(ColorBlue),
+        BlockId::BlueCarpet => Thin { color: ColorBlue },
         BlockId::BlueConcrete => Single(ColorBlue),
         BlockId::BlueConcretePowder => Single(ColorBlue),
         BlockId::BlueGlazedTerracotta => Single(ColorBlue),
@@ -136,15 +137,15 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::BrainCoralFan => Single(ColorPink),
         BlockId::BrainCoralWallFan => Single(ColorPink),
         BlockId::BrewingStand => Single(Metal),
-        BlockId::BrickSlab => Single(ColorRed),
-        BlockId::BrickStairs => Single(ColorRed),
+        BlockId::BrickSlab => Slab { color: ColorRed },
+        BlockId::BrickStairs => Stairs { color: ColorRed },
         BlockId::BrickWall => Single(ColorRed),
         BlockId::Bricks => Single(ColorRed),
         BlockId::BrownBanner => Single(Wood),
         BlockId::BrownBed => Bed { head: Wool, foot: ColorBrown },
         BlockId::BrownCandle => Single(ColorBrown),
         BlockId::BrownCandleCake => Single(None),
-        BlockId::BrownCarpet => Single(ColorBrown),
+        BlockId::BrownCarpet => Thin { color: ColorBrown },
         BlockId::BrownConcrete => Single(ColorBrown),
         BlockId::BrownConcretePowder => Single(ColorBrown),
         BlockId::BrownGlazedTerracotta => Single(ColorBrown),
@@ -156,7 +157,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::BrownTerracotta => Single(TerracottaBrown),
         BlockId::BrownWallBanner => Single(Wood),
         BlockId::BrownWool => Single(ColorBrown),
-        BlockId::BubbleColumn => Single(Water),
+        BlockId::BubbleColumn => Biome { color: Water, kind: BiomeColorKind::Water },
         BlockId::BubbleCoral => Single(ColorPurple),
         BlockId::BubbleCoralBlock => Single(ColorPurple),
         BlockId::BubbleCoralFan => Single(ColorPurple),
@@ -187,12 +188,12 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::CherryLeaves => Single(ColorPink),
         BlockId::CherryLog => Pillar { top: TerracottaWhite, side: TerracottaGray },
         BlockId::CherryPlanks => Single(TerracottaWhite),
-        BlockId::CherryPressurePlate => Single(TerracottaWhite),
+        BlockId::CherryPressurePlate => Thin { color: TerracottaWhite },
         BlockId::CherrySapling => Single(ColorPink),
         BlockId::CherryShelf => Single(TerracottaWhite),
         BlockId::CherrySign => Single(TerracottaWhite),
-        BlockId::CherrySlab => Single(TerracottaWhite),
-        BlockId::CherryStairs => Single(TerracottaWhite),
+        BlockId::CherrySlab => Slab { color: TerracottaWhite },
+        BlockId::CherryStairs => Stairs { color: TerracottaWhite },
         BlockId::CherryTrapdoor => Single(TerracottaWhite),
         BlockId::CherryWallHangingSign => Single(TerracottaPink),
         BlockId::CherryWallSign => Single(TerracottaWhite),
@@ -219,12 +220,12 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::CoalOre => Single(Stone),
         BlockId::CoarseDirt => Single(Dirt),
         BlockId::CobbledDeepslate => Single(Deepslate),
-        BlockId::CobbledDeepslateSlab => Single(Deepslate),
-        BlockId::CobbledDeepslateStairs => Single(Deepslate),
+        BlockId::CobbledDeepslateSlab => Slab { color: Deepslate },
+        BlockId::CobbledDeepslateStairs => Stairs { color: Deepslate },
         BlockId::CobbledDeepslateWall => Single(Deepslate),
         BlockId::Cobblestone => Single(Stone),
-        BlockId::CobblestoneSlab => Single(Stone),
-        BlockId::CobblestoneStairs => Single(Stone),
+        BlockId::CobblestoneSlab => Slab { color: Stone },
+        BlockId::CobblestoneStairs => Stairs { color: Stone },
         BlockId::CobblestoneWall => Single(Stone),
         BlockId::Cobweb => Single(Wool),
         BlockId::Cocoa => Single(Plant),
@@ -265,29 +266,29 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::CrimsonHyphae => Single(CrimsonHyphae),
         BlockId::CrimsonNylium => Single(CrimsonNylium),
         BlockId::CrimsonPlanks => Single(CrimsonStem),
-        BlockId::CrimsonPressurePlate => Single(CrimsonStem),
+        BlockId::CrimsonPressurePlate => Thin { color: CrimsonStem },
         BlockId::CrimsonRoots => Single(Nether),
         BlockId::CrimsonShelf => Single(CrimsonStem),
         BlockId::CrimsonSign => Single(CrimsonStem),
-        BlockId::CrimsonSlab => Single(CrimsonStem),
-        BlockId::CrimsonStairs => Single(CrimsonStem),
+        BlockId::CrimsonSlab => Slab { color: CrimsonStem },
+        BlockId::CrimsonStairs => Stairs { color: CrimsonStem },
         BlockId::CrimsonStem => Single(CrimsonStem),
         BlockId::CrimsonTrapdoor => Single(CrimsonStem),
         BlockId::CrimsonWallHangingSign => Single(CrimsonStem),
         BlockId::CrimsonWallSign => Single(CrimsonStem),
         BlockId::CryingObsidian => Single(ColorBlack),
         BlockId::CutCopper => Single(ColorOrange),
-        BlockId::CutCopperSlab => Single(ColorOrange),
-        BlockId::CutCopperStairs => Single(ColorOrange),
+        BlockId::CutCopperSlab => Slab { color: ColorOrange },
+        BlockId::CutCopperStairs => Stairs { color: ColorOrange },
         BlockId::CutRedSandstone => Single(ColorOrange),
-        BlockId::CutRedSandstoneSlab => Single(ColorOrange),
+        BlockId::CutRedSandstoneSlab => Slab { color: ColorOrange },
         BlockId::CutSandstone => Single(Sand),
-        BlockId::CutSandstoneSlab => Single(Sand),
+        BlockId::CutSandstoneSlab => Slab { color: Sand },
         BlockId::CyanBanner => Single(Wood),
         BlockId::CyanBed => Bed { head: Wool, foot: ColorCyan },
         BlockId::CyanCandle => Single(ColorCyan),
         BlockId::CyanCandleCake => Single(None),
-        BlockId::CyanCarpet => Single(ColorCyan),
+        BlockId::CyanCarpet => Thin { color: ColorCyan },
         BlockId::CyanConcrete => Single(ColorCyan),
         BlockId::CyanConcretePowder => Single(ColorCyan),
         BlockId::CyanGlazedTerracotta => Single(ColorCyan),
@@ -304,22 +305,22 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::DarkOakFence => Single(ColorBrown),
         BlockId::DarkOakFenceGate => Single(ColorBrown),
         BlockId::DarkOakHangingSign => Single(ColorBrown),
-        BlockId::DarkOakLeaves => Single(Plant),
+        BlockId::DarkOakLeaves => Biome { color: Plant, kind: BiomeColorKind::Foliage },
         BlockId::DarkOakLog => Pillar { top: ColorBrown, side: ColorBrown },
         BlockId::DarkOakPlanks => Single(ColorBrown),
-        BlockId::DarkOakPressurePlate => Single(ColorBrown),
+        BlockId::DarkOakPressurePlate => Thin { color: ColorBrown },
         BlockId::DarkOakSapling => Single(Plant),
         BlockId::DarkOakShelf => Single(ColorBrown),
         BlockId::DarkOakSign => Single(ColorBrown),
-        BlockId::DarkOakSlab => Single(ColorBrown),
-        BlockId::DarkOakStairs => Single(ColorBrown),
+        BlockId::DarkOakSlab => Slab { color: ColorBrown },
+        BlockId::DarkOakStairs => Stairs { color: ColorBrown },
         BlockId::DarkOakTrapdoor => Single(ColorBrown),
         BlockId::DarkOakWallHangingSign => Single(ColorBrown),
         BlockId::DarkOakWallSign => Single(ColorBrown),
         BlockId::DarkOakWood => Single(ColorBrown),
         BlockId::DarkPrismarine => Single(Diamond),
-        BlockId::DarkPrismarineSlab => Single(Diamond),
-        BlockId::DarkPrismarineStairs => Single(Diamond),
+        BlockId::DarkPrismarineSlab => Slab { color: Diamond },
+        BlockId::DarkPrismarineStairs => Stairs { color: Diamond },
         BlockId::DaylightDetector => Single(Wood),
         BlockId::DeadBrainCoral => Single(ColorGray),
         BlockId::DeadBrainCoralBlock => Single(ColorGray),
@@ -344,8 +345,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::DeadTubeCoralWallFan => Single(ColorGray),
         BlockId::DecoratedPot => Single(TerracottaRed),
         BlockId::Deepslate => Single(Deepslate),
-        BlockId::DeepslateBrickSlab => Single(Deepslate),
-        BlockId::DeepslateBrickStairs => Single(Deepslate),
+        BlockId::DeepslateBrickSlab => Slab { color: Deepslate },
+        BlockId::DeepslateBrickStairs => Stairs { color: Deepslate },
         BlockId::DeepslateBrickWall => Single(Deepslate),
         BlockId::DeepslateBricks => Single(Deepslate),
         BlockId::DeepslateCoalOre => Single(Deepslate),
@@ -356,16 +357,16 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::DeepslateIronOre => Single(Deepslate),
         BlockId::DeepslateLapisOre => Single(Deepslate),
         BlockId::DeepslateRedstoneOre => Single(Deepslate),
-        BlockId::DeepslateTileSlab => Single(Deepslate),
-        BlockId::DeepslateTileStairs => Single(Deepslate),
+        BlockId::DeepslateTileSlab => Slab { color: Deepslate },
+        BlockId::DeepslateTileStairs => Stairs { color: Deepslate },
         BlockId::DeepslateTileWall => Single(Deepslate),
         BlockId::DeepslateTiles => Single(Deepslate),
         BlockId::DetectorRail => Single(None),
         BlockId::DiamondBlock => Single(Diamond),
         BlockId::DiamondOre => Single(Stone),
         BlockId::Diorite => Single(Quartz),
-        BlockId::DioriteSlab => Single(Quartz),
-        BlockId::DioriteStairs => Single(Quartz),
+        BlockId::DioriteSlab => Slab { color: Quartz },
+        BlockId::DioriteStairs => Stairs { color: Quartz },
         BlockId::DioriteWall => Single(Quartz),
         BlockId::Dirt => Single(Dirt),
         BlockId::DirtPath => Single(Dirt),
@@ -385,8 +386,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::EndPortalFrame => Single(ColorGreen),
         BlockId::EndRod => Single(None),
         BlockId::EndStone => Single(Sand),
-        BlockId::EndStoneBrickSlab => Single(Sand),
-        BlockId::EndStoneBrickStairs => Single(Sand),
+        BlockId::EndStoneBrickSlab => Slab { color: Sand },
+        BlockId::EndStoneBrickStairs => Stairs { color: Sand },
         BlockId::EndStoneBrickWall => Single(Sand),
         BlockId::EndStoneBricks => Single(Sand),
         BlockId::EnderChest => Single(Stone),
@@ -399,11 +400,11 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::ExposedCopperGrate => Single(TerracottaLightGray),
         BlockId::ExposedCopperTrapdoor => Single(TerracottaLightGray),
         BlockId::ExposedCutCopper => Single(TerracottaLightGray),
-        BlockId::ExposedCutCopperSlab => Single(TerracottaLightGray),
-        BlockId::ExposedCutCopperStairs => Single(TerracottaLightGray),
+        BlockId::ExposedCutCopperSlab => Slab { color: TerracottaLightGray },
+        BlockId::ExposedCutCopperStairs => Stairs { color: TerracottaLightGray },
         BlockId::ExposedLightningRod => Single(TerracottaLightGray),
         BlockId::Farmland => Single(Dirt),
-        BlockId::Fern => Single(Plant),
+        BlockId::Fern => Biome { color: Plant, kind: BiomeColorKind::Grass },
         BlockId::Fire => Single(Fire),
         BlockId::FireCoral => Single(ColorRed),
         BlockId::FireCoralBlock => Single(ColorRed),
@@ -426,16 +427,16 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::GoldOre => Single(Stone),
         BlockId::GoldenDandelion => Single(Plant),
         BlockId::Granite => Single(Dirt),
-        BlockId::GraniteSlab => Single(Dirt),
-        BlockId::GraniteStairs => Single(Dirt),
+        BlockId::GraniteSlab => Slab { color: Dirt },
+        BlockId::GraniteStairs => Stairs { color: Dirt },
         BlockId::GraniteWall => Single(Dirt),
-        BlockId::GrassBlock => Single(Grass),
+        BlockId::GrassBlock => Biome { color: Grass, kind: BiomeColorKind::Grass },
         BlockId::Gravel => Single(Stone),
         BlockId::GrayBanner => Single(Wood),
         BlockId::GrayBed => Bed { head: Wool, foot: ColorGray },
         BlockId::GrayCandle => Single(ColorGray),
         BlockId::GrayCandleCake => Single(None),
-        BlockId::GrayCarpet => Single(ColorGray),
+        BlockId::GrayCarpet => Thin { color: ColorGray },
         BlockId::GrayConcrete => Single(ColorGray),
         BlockId::GrayConcretePowder => Single(ColorGray),
         BlockId::GrayGlazedTerracotta => Single(ColorGray),
@@ -449,7 +450,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::GreenBed => Bed { head: Wool, foot: ColorGreen },
         BlockId::GreenCandle => Single(ColorGreen),
         BlockId::GreenCandleCake => Single(None),
-        BlockId::GreenCarpet => Single(ColorGreen),
+        BlockId::GreenCarpet => Thin { color: ColorGreen },
         BlockId::GreenConcrete => Single(ColorGreen),
         BlockId::GreenConcretePowder => Single(ColorGreen),
         BlockId::GreenGlazedTerracotta => Single(ColorGreen),
@@ -463,7 +464,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::HangingRoots => Single(Dirt),
         BlockId::HayBlock => Single(ColorYellow),
         BlockId::HeavyCore => Single(Metal),
-        BlockId::HeavyWeightedPressurePlate => Single(Metal),
+        BlockId::HeavyWeightedPressurePlate => Thin { color: Metal },
         BlockId::HoneyBlock => Single(ColorOrange),
         BlockId::HoneycombBlock => Single(ColorOrange),
         BlockId::Hopper => Single(Stone),
@@ -493,15 +494,15 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::JungleFence => Single(Dirt),
         BlockId::JungleFenceGate => Single(Dirt),
         BlockId::JungleHangingSign => Single(Dirt),
-        BlockId::JungleLeaves => Single(Plant),
+        BlockId::JungleLeaves => Biome { color: Plant, kind: BiomeColorKind::Foliage },
         BlockId::JungleLog => Pillar { top: Dirt, side: Podzol },
         BlockId::JunglePlanks => Single(Dirt),
-        BlockId::JunglePressurePlate => Single(Dirt),
+        BlockId::JunglePressurePlate => Thin { color: Dirt },
         BlockId::JungleSapling => Single(Plant),
         BlockId::JungleShelf => Single(Dirt),
         BlockId::JungleSign => Single(Dirt),
-        BlockId::JungleSlab => Single(Dirt),
-        BlockId::JungleStairs => Single(Dirt),
+        BlockId::JungleSlab => Slab { color: Dirt },
+        BlockId::JungleStairs => Stairs { color: Dirt },
         BlockId::JungleTrapdoor => Single(Dirt),
         BlockId::JungleWallHangingSign => Single(Dirt),
         BlockId::JungleWallSign => Single(Dirt),
@@ -513,7 +514,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::LapisBlock => Single(Lapis),
         BlockId::LapisOre => Single(Stone),
         BlockId::LargeAmethystBud => Single(ColorPurple),
-        BlockId::LargeFern => Single(Plant),
+        BlockId::LargeFern => Biome { color: Plant, kind: BiomeColorKind::Grass },
         BlockId::Lava => Single(Fire),
         BlockId::LavaCauldron => Single(Stone),
         BlockId::LeafLitter => Single(ColorBrown),
@@ -524,7 +525,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::LightBlueBed => Bed { head: Wool, foot: ColorLightBlue },
         BlockId::LightBlueCandle => Single(ColorLightBlue),
         BlockId::LightBlueCandleCake => Single(None),
-        BlockId::LightBlueCarpet => Single(ColorLightBlue),
+        BlockId::LightBlueCarpet => Thin { color: ColorLightBlue },
         BlockId::LightBlueConcrete => Single(ColorLightBlue),
         BlockId::LightBlueConcretePowder => Single(ColorLightBlue),
         BlockId::LightBlueGlazedTerracotta => Single(ColorLightBlue),
@@ -538,7 +539,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::LightGrayBed => Bed { head: Wool, foot: ColorLightGray },
         BlockId::LightGrayCandle => Single(ColorLightGray),
         BlockId::LightGrayCandleCake => Single(None),
-        BlockId::LightGrayCarpet => Single(ColorLightGray),
+        BlockId::LightGrayCarpet => Thin { color: ColorLightGray },
         BlockId::LightGrayConcrete => Single(ColorLightGray),
         BlockId::LightGrayConcretePowder => Single(ColorLightGray),
         BlockId::LightGrayGlazedTerracotta => Single(ColorLightGray),
@@ -548,16 +549,16 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::LightGrayTerracotta => Single(TerracottaLightGray),
         BlockId::LightGrayWallBanner => Single(Wood),
         BlockId::LightGrayWool => Single(ColorLightGray),
-        BlockId::LightWeightedPressurePlate => Single(Gold),
+        BlockId::LightWeightedPressurePlate => Thin { color: Gold },
         BlockId::LightningRod => Single(ColorOrange),
         BlockId::Lilac => Single(Plant),
         BlockId::LilyOfTheValley => Single(Plant),
-        BlockId::LilyPad => Single(Plant),
+        BlockId::LilyPad => Thin { color: Plant },
         BlockId::LimeBanner => Single(Wood),
         BlockId::LimeBed => Bed { head: Wool, foot: ColorLightGreen },
         BlockId::LimeCandle => Single(ColorLightGreen),
         BlockId::LimeCandleCake => Single(None),
-        BlockId::LimeCarpet => Single(ColorLightGreen),
+        BlockId::LimeCarpet => Thin { color: ColorLightGreen },
         BlockId::LimeConcrete => Single(ColorLightGreen),
         BlockId::LimeConcretePowder => Single(ColorLightGreen),
         BlockId::LimeGlazedTerracotta => Single(ColorLightGreen),
@@ -573,7 +574,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::MagentaBed => Bed { head: Wool, foot: ColorMagenta },
         BlockId::MagentaCandle => Single(ColorMagenta),
         BlockId::MagentaCandleCake => Single(None),
-        BlockId::MagentaCarpet => Single(ColorMagenta),
+        BlockId::MagentaCarpet => Thin { color: ColorMagenta },
         BlockId::MagentaConcrete => Single(ColorMagenta),
         BlockId::MagentaConcretePowder => Single(ColorMagenta),
         BlockId::MagentaGlazedTerracotta => Single(ColorMagenta),
@@ -589,16 +590,16 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::MangroveFence => Single(ColorRed),
         BlockId::MangroveFenceGate => Single(ColorRed),
         BlockId::MangroveHangingSign => Single(ColorRed),
-        BlockId::MangroveLeaves => Single(Plant),
+        BlockId::MangroveLeaves => Biome { color: Plant, kind: BiomeColorKind::Foliage },
         BlockId::MangroveLog => Pillar { top: ColorRed, side: Podzol },
         BlockId::MangrovePlanks => Single(ColorRed),
-        BlockId::MangrovePressurePlate => Single(ColorRed),
+        BlockId::MangrovePressurePlate => Thin { color: ColorRed },
         BlockId::MangrovePropagule => Single(Plant),
         BlockId::MangroveRoots => Single(Podzol),
         BlockId::MangroveShelf => Single(ColorRed),
         BlockId::MangroveSign => Single(ColorRed),
-        BlockId::MangroveSlab => Single(ColorRed),
-        BlockId::MangroveStairs => Single(ColorRed),
+        BlockId::MangroveSlab => Slab { color: ColorRed },
+        BlockId::MangroveStairs => Stairs { color: ColorRed },
         BlockId::MangroveTrapdoor => Single(ColorRed),
         BlockId::MangroveWallHangingSign => Single(ColorRed),
         BlockId::MangroveWallSign => Single(ColorRed),
@@ -607,27 +608,27 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::Melon => Single(ColorLightGreen),
         BlockId::MelonStem => Single(Plant),
         BlockId::MossBlock => Single(ColorGreen),
-        BlockId::MossCarpet => Single(ColorGreen),
+        BlockId::MossCarpet => Thin { color: ColorGreen },
         BlockId::MossyCobblestone => Single(Stone),
-        BlockId::MossyCobblestoneSlab => Single(Stone),
-        BlockId::MossyCobblestoneStairs => Single(Stone),
+        BlockId::MossyCobblestoneSlab => Slab { color: Stone },
+        BlockId::MossyCobblestoneStairs => Stairs { color: Stone },
         BlockId::MossyCobblestoneWall => Single(Stone),
-        BlockId::MossyStoneBrickSlab => Single(Stone),
-        BlockId::MossyStoneBrickStairs => Single(Stone),
+        BlockId::MossyStoneBrickSlab => Slab { color: Stone },
+        BlockId::MossyStoneBrickStairs => Stairs { color: Stone },
         BlockId::MossyStoneBrickWall => Single(Stone),
         BlockId::MossyStoneBricks => Single(Stone),
         BlockId::MovingPiston => Single(Stone),
         BlockId::Mud => Single(TerracottaCyan),
-        BlockId::MudBrickSlab => Single(TerracottaLightGray),
-        BlockId::MudBrickStairs => Single(TerracottaLightGray),
+        BlockId::MudBrickSlab => Slab { color: TerracottaLightGray },
+        BlockId::MudBrickStairs => Stairs { color: TerracottaLightGray },
         BlockId::MudBrickWall => Single(TerracottaLightGray),
         BlockId::MudBricks => Single(TerracottaLightGray),
         BlockId::MuddyMangroveRoots => Single(Podzol),
         BlockId::MushroomStem => Single(Wool),
         BlockId::Mycelium => Single(ColorPurple),
         BlockId::NetherBrickFence => Single(Nether),
-        BlockId::NetherBrickSlab => Single(Nether),
-        BlockId::NetherBrickStairs => Single(Nether),
+        BlockId::NetherBrickSlab => Slab { color: Nether },
+        BlockId::NetherBrickStairs => Stairs { color: Nether },
         BlockId::NetherBrickWall => Single(Nether),
         BlockId::NetherBricks => Single(Nether),
         BlockId::NetherGoldOre => Single(Nether),
@@ -644,15 +645,15 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::OakFence => Single(Wood),
         BlockId::OakFenceGate => Single(Wood),
         BlockId::OakHangingSign => Single(Wood),
-        BlockId::OakLeaves => Single(Plant),
+        BlockId::OakLeaves => Biome { color: Plant, kind: BiomeColorKind::Foliage },
         BlockId::OakLog => Pillar { top: Wood, side: Podzol },
         BlockId::OakPlanks => Single(Wood),
-        BlockId::OakPressurePlate => Single(Wood),
+        BlockId::OakPressurePlate => Thin { color: Wood },
         BlockId::OakSapling => Single(Plant),
         BlockId::OakShelf => Single(Wood),
         BlockId::OakSign => Single(Wood),
-        BlockId::OakSlab => Single(Wood),
-        BlockId::OakStairs => Single(Wood),
+        BlockId::OakSlab => Slab { color: Wood },
+        BlockId::OakStairs => Stairs { color: Wood },
         BlockId::OakTrapdoor => Single(Wood),
         BlockId::OakWallHangingSign => Single(Wood),
         BlockId::OakWallSign => Single(Wood),
@@ -665,7 +666,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::OrangeBed => Bed { head: Wool, foot: ColorOrange },
         BlockId::OrangeCandle => Single(ColorOrange),
         BlockId::OrangeCandleCake => Single(None),
-        BlockId::OrangeCarpet => Single(ColorOrange),
+        BlockId::OrangeCarpet => Thin { color: ColorOrange },
         BlockId::OrangeConcrete => Single(ColorOrange),
         BlockId::OrangeConcretePowder => Single(ColorOrange),
         BlockId::OrangeGlazedTerracotta => Single(ColorOrange),
@@ -686,14 +687,14 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::OxidizedCopperGrate => Single(WarpedNylium),
         BlockId::OxidizedCopperTrapdoor => Single(WarpedNylium),
         BlockId::OxidizedCutCopper => Single(WarpedNylium),
-        BlockId::OxidizedCutCopperSlab => Single(WarpedNylium),
-        BlockId::OxidizedCutCopperStairs => Single(WarpedNylium),
+        BlockId::OxidizedCutCopperSlab => Slab { color: WarpedNylium },
+        BlockId::OxidizedCutCopperStairs => Stairs { color: WarpedNylium },
         BlockId::OxidizedLightningRod => Single(WarpedNylium),
         BlockId::PackedIce => Single(Ice),
         BlockId::PackedMud => Single(Dirt),
         BlockId::PaleHangingMoss => Single(ColorLightGray),
         BlockId::PaleMossBlock => Single(ColorLightGray),
-        BlockId::PaleMossCarpet => Single(ColorLightGray),
+        BlockId::PaleMossCarpet => Thin { color: ColorLightGray },
         BlockId::PaleOakButton => Single(None),
         BlockId::PaleOakDoor => Single(Quartz),
         BlockId::PaleOakFence => Single(Quartz),
@@ -702,26 +703,26 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::PaleOakLeaves => Single(Metal),
         BlockId::PaleOakLog => Pillar { top: Quartz, side: Stone },
         BlockId::PaleOakPlanks => Single(Quartz),
-        BlockId::PaleOakPressurePlate => Single(Quartz),
+        BlockId::PaleOakPressurePlate => Thin { color: Quartz },
         BlockId::PaleOakSapling => Single(Metal),
         BlockId::PaleOakShelf => Single(Quartz),
         BlockId::PaleOakSign => Single(Quartz),
-        BlockId::PaleOakSlab => Single(Quartz),
-        BlockId::PaleOakStairs => Single(Quartz),
+        BlockId::PaleOakSlab => Slab { color: Quartz },
+        BlockId::PaleOakStairs => Stairs { color: Quartz },
         BlockId::PaleOakTrapdoor => Single(Quartz),
         BlockId::PaleOakWallHangingSign => Single(Quartz),
         BlockId::PaleOakWallSign => Single(Quartz),
         BlockId::PaleOakWood => Single(Stone),
         BlockId::PearlescentFroglight => Single(ColorPink),
         BlockId::Peony => Single(Plant),
-        BlockId::PetrifiedOakSlab => Single(Wood),
+        BlockId::PetrifiedOakSlab => Slab { color: Wood },
         BlockId::PiglinHead => Single(None),
         BlockId::PiglinWallHead => Single(None),
         BlockId::PinkBanner => Single(Wood),
         BlockId::PinkBed => Bed { head: Wool, foot: ColorPink },
         BlockId::PinkCandle => Single(ColorPink),
         BlockId::PinkCandleCake => Single(None),
-        BlockId::PinkCarpet => Single(ColorPink),
+        BlockId::PinkCarpet => Thin { color: ColorPink },
         BlockId::PinkConcrete => Single(ColorPink),
         BlockId::PinkConcretePowder => Single(ColorPink),
         BlockId::PinkGlazedTerracotta => Single(ColorPink),
@@ -742,32 +743,32 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::Podzol => Single(Podzol),
         BlockId::PointedDripstone => Single(TerracottaBrown),
         BlockId::PolishedAndesite => Single(Stone),
-        BlockId::PolishedAndesiteSlab => Single(Stone),
-        BlockId::PolishedAndesiteStairs => Single(Stone),
+        BlockId::PolishedAndesiteSlab => Slab { color: Stone },
+        BlockId::PolishedAndesiteStairs => Stairs { color: Stone },
         BlockId::PolishedBasalt => Single(ColorBlack),
         BlockId::PolishedBlackstone => Single(ColorBlack),
-        BlockId::PolishedBlackstoneBrickSlab => Single(ColorBlack),
-        BlockId::PolishedBlackstoneBrickStairs => Single(ColorBlack),
+        BlockId::PolishedBlackstoneBrickSlab => Slab { color: ColorBlack },
+        BlockId::PolishedBlackstoneBrickStairs => Stairs { color: ColorBlack },
         BlockId::PolishedBlackstoneBrickWall => Single(ColorBlack),
         BlockId::PolishedBlackstoneBricks => Single(ColorBlack),
         BlockId::PolishedBlackstoneButton => Single(None),
-        BlockId::PolishedBlackstonePressurePlate => Single(ColorBlack),
-        BlockId::PolishedBlackstoneSlab => Single(ColorBlack),
-        BlockId::PolishedBlackstoneStairs => Single(ColorBlack),
+        BlockId::PolishedBlackstonePressurePlate => Thin { color: ColorBlack },
+        BlockId::PolishedBlackstoneSlab => Slab { color: ColorBlack },
+        BlockId::PolishedBlackstoneStairs => Stairs { color: ColorBlack },
         BlockId::PolishedBlackstoneWall => Single(ColorBlack),
         BlockId::PolishedDeepslate => Single(Deepslate),
-        BlockId::PolishedDeepslateSlab => Single(Deepslate),
-        BlockId::PolishedDeepslateStairs => Single(Deepslate),
+        BlockId::PolishedDeepslateSlab => Slab { color: Deepslate },
+        BlockId::PolishedDeepslateStairs => Stairs { color: Deepslate },
         BlockId::PolishedDeepslateWall => Single(Deepslate),
         BlockId::PolishedDiorite => Single(Quartz),
-        BlockId::PolishedDioriteSlab => Single(Quartz),
-        BlockId::PolishedDioriteStairs => Single(Quartz),
+        BlockId::PolishedDioriteSlab => Slab { color: Quartz },
+        BlockId::PolishedDioriteStairs => Stairs { color: Quartz },
         BlockId::PolishedGranite => Single(Dirt),
-        BlockId::PolishedGraniteSlab => Single(Dirt),
-        BlockId::PolishedGraniteStairs => Single(Dirt),
+        BlockId::PolishedGraniteSlab => Slab { color: Dirt },
+        BlockId::PolishedGraniteStairs => Stairs { color: Dirt },
         BlockId::PolishedTuff => Single(TerracottaGray),
-        BlockId::PolishedTuffSlab => Single(TerracottaGray),
-        BlockId::PolishedTuffStairs => Single(TerracottaGray),
+        BlockId::PolishedTuffSlab => Slab { color: TerracottaGray },
+        BlockId::PolishedTuffStairs => Stairs { color: TerracottaGray },
         BlockId::PolishedTuffWall => Single(TerracottaGray),
         BlockId::Poppy => Single(Plant),
         BlockId::Potatoes => Single(Plant),
@@ -813,11 +814,11 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::PowderSnowCauldron => Single(Stone),
         BlockId::PoweredRail => Single(None),
         BlockId::Prismarine => Single(ColorCyan),
-        BlockId::PrismarineBrickSlab => Single(Diamond),
-        BlockId::PrismarineBrickStairs => Single(Diamond),
+        BlockId::PrismarineBrickSlab => Slab { color: Diamond },
+        BlockId::PrismarineBrickStairs => Stairs { color: Diamond },
         BlockId::PrismarineBricks => Single(Diamond),
-        BlockId::PrismarineSlab => Single(ColorCyan),
-        BlockId::PrismarineStairs => Single(ColorCyan),
+        BlockId::PrismarineSlab => Slab { color: ColorCyan },
+        BlockId::PrismarineStairs => Stairs { color: ColorCyan },
         BlockId::PrismarineWall => Single(ColorCyan),
         BlockId::Pumpkin => Single(ColorOrange),
         BlockId::PumpkinStem => Single(Plant),
@@ -825,7 +826,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::PurpleBed => Bed { head: Wool, foot: ColorPurple },
         BlockId::PurpleCandle => Single(ColorPurple),
         BlockId::PurpleCandleCake => Single(None),
-        BlockId::PurpleCarpet => Single(ColorPurple),
+        BlockId::PurpleCarpet => Thin { color: ColorPurple },
         BlockId::PurpleConcrete => Single(ColorPurple),
         BlockId::PurpleConcretePowder => Single(ColorPurple),
         BlockId::PurpleGlazedTerracotta => Single(ColorPurple),
@@ -837,13 +838,13 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::PurpleWool => Single(ColorPurple),
         BlockId::PurpurBlock => Single(ColorMagenta),
         BlockId::PurpurPillar => Single(ColorMagenta),
-        BlockId::PurpurSlab => Single(ColorMagenta),
-        BlockId::PurpurStairs => Single(ColorMagenta),
+        BlockId::PurpurSlab => Slab { color: ColorMagenta },
+        BlockId::PurpurStairs => Stairs { color: ColorMagenta },
         BlockId::QuartzBlock => Single(Quartz),
         BlockId::QuartzBricks => Single(Quartz),
         BlockId::QuartzPillar => Single(Quartz),
-        BlockId::QuartzSlab => Single(Quartz),
-        BlockId::QuartzStairs => Single(Quartz),
+        BlockId::QuartzSlab => Slab { color: Quartz },
+        BlockId::QuartzStairs => Stairs { color: Quartz },
         BlockId::Rail => Single(None),
         BlockId::RawCopperBlock => Single(ColorOrange),
         BlockId::RawGoldBlock => Single(Gold),
@@ -852,20 +853,20 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::RedBed => Bed { head: Wool, foot: ColorRed },
         BlockId::RedCandle => Single(ColorRed),
         BlockId::RedCandleCake => Single(None),
-        BlockId::RedCarpet => Single(ColorRed),
+        BlockId::RedCarpet => Thin { color: ColorRed },
         BlockId::RedConcrete => Single(ColorRed),
         BlockId::RedConcretePowder => Single(ColorRed),
         BlockId::RedGlazedTerracotta => Single(ColorRed),
         BlockId::RedMushroom => Single(ColorRed),
         BlockId::RedMushroomBlock => Single(ColorRed),
-        BlockId::RedNetherBrickSlab => Single(Nether),
-        BlockId::RedNetherBrickStairs => Single(Nether),
+        BlockId::RedNetherBrickSlab => Slab { color: Nether },
+        BlockId::RedNetherBrickStairs => Stairs { color: Nether },
         BlockId::RedNetherBrickWall => Single(Nether),
         BlockId::RedNetherBricks => Single(Nether),
         BlockId::RedSand => Single(ColorOrange),
         BlockId::RedSandstone => Single(ColorOrange),
-        BlockId::RedSandstoneSlab => Single(ColorOrange),
-        BlockId::RedSandstoneStairs => Single(ColorOrange),
+        BlockId::RedSandstoneSlab => Slab { color: ColorOrange },
+        BlockId::RedSandstoneStairs => Stairs { color: ColorOrange },
         BlockId::RedSandstoneWall => Single(ColorOrange),
         BlockId::RedShulkerBox => Single(ColorRed),
         BlockId::RedStainedGlass => Single(ColorRed),
@@ -884,8 +885,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::Repeater => Single(None),
         BlockId::RepeatingCommandBlock => Single(ColorPurple),
         BlockId::ResinBlock => Single(TerracottaOrange),
-        BlockId::ResinBrickSlab => Single(TerracottaOrange),
-        BlockId::ResinBrickStairs => Single(TerracottaOrange),
+        BlockId::ResinBrickSlab => Slab { color: TerracottaOrange },
+        BlockId::ResinBrickStairs => Stairs { color: TerracottaOrange },
         BlockId::ResinBrickWall => Single(TerracottaOrange),
         BlockId::ResinBricks => Single(TerracottaOrange),
         BlockId::ResinClump => Single(TerracottaOrange),
@@ -894,8 +895,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::RoseBush => Single(Plant),
         BlockId::Sand => Single(Sand),
         BlockId::Sandstone => Single(Sand),
-        BlockId::SandstoneSlab => Single(Sand),
-        BlockId::SandstoneStairs => Single(Sand),
+        BlockId::SandstoneSlab => Slab { color: Sand },
+        BlockId::SandstoneStairs => Stairs { color: Sand },
         BlockId::SandstoneWall => Single(Sand),
         BlockId::Scaffolding => Single(Sand),
         BlockId::Sculk => Single(ColorBlack),
@@ -907,7 +908,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::SeaPickle => Single(ColorGreen),
         BlockId::Seagrass => Single(Water),
         BlockId::ShortDryGrass => Single(ColorYellow),
-        BlockId::ShortGrass => Single(Plant),
+        BlockId::ShortGrass => Biome { color: Plant, kind: BiomeColorKind::Grass },
         BlockId::Shroomlight => Single(ColorRed),
         BlockId::ShulkerBox => Single(ColorPurple),
         BlockId::SkeletonSkull => Single(None),
@@ -919,18 +920,18 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::Smoker => Single(Stone),
         BlockId::SmoothBasalt => Single(ColorBlack),
         BlockId::SmoothQuartz => Single(Quartz),
-        BlockId::SmoothQuartzSlab => Single(Quartz),
-        BlockId::SmoothQuartzStairs => Single(Quartz),
+        BlockId::SmoothQuartzSlab => Slab { color: Quartz },
+        BlockId::SmoothQuartzStairs => Stairs { color: Quartz },
         BlockId::SmoothRedSandstone => Single(ColorOrange),
-        BlockId::SmoothRedSandstoneSlab => Single(ColorOrange),
-        BlockId::SmoothRedSandstoneStairs => Single(ColorOrange),
+        BlockId::SmoothRedSandstoneSlab => Slab { color: ColorOrange },
+        BlockId::SmoothRedSandstoneStairs => Stairs { color: ColorOrange },
         BlockId::SmoothSandstone => Single(Sand),
-        BlockId::SmoothSandstoneSlab => Single(Sand),
-        BlockId::SmoothSandstoneStairs => Single(Sand),
+        BlockId::SmoothSandstoneSlab => Slab { color: Sand },
+        BlockId::SmoothSandstoneStairs => Stairs { color: Sand },
         BlockId::SmoothStone => Single(Stone),
-        BlockId::SmoothStoneSlab => Single(Stone),
+        BlockId::SmoothStoneSlab => Slab { color: Stone },
         BlockId::SnifferEgg => Single(ColorRed),
-        BlockId::Snow => Single(Snow),
+        BlockId::Snow => Layers { color: Snow },
         BlockId::SnowBlock => Single(Snow),
         BlockId::SoulCampfire => Single(Podzol),
         BlockId::SoulFire => Single(ColorLightBlue),
@@ -950,26 +951,26 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::SpruceLeaves => Single(Plant),
         BlockId::SpruceLog => Pillar { top: Podzol, side: ColorBrown },
         BlockId::SprucePlanks => Single(Podzol),
-        BlockId::SprucePressurePlate => Single(Podzol),
+        BlockId::SprucePressurePlate => Thin { color: Podzol },
         BlockId::SpruceSapling => Single(Plant),
         BlockId::SpruceShelf => Single(Podzol),
         BlockId::SpruceSign => Single(Podzol),
-        BlockId::SpruceSlab => Single(Podzol),
-        BlockId::SpruceStairs => Single(Podzol),
+        BlockId::SpruceSlab => Slab { color: Podzol },
+        BlockId::SpruceStairs => Stairs { color: Podzol },
         BlockId::SpruceTrapdoor => Single(Podzol),
         BlockId::SpruceWallHangingSign => Single(Wood),
         BlockId::SpruceWallSign => Single(Podzol),
         BlockId::SpruceWood => Single(Podzol),
         BlockId::StickyPiston => Single(Stone),
         BlockId::Stone => Single(Stone),
-        BlockId::StoneBrickSlab => Single(Stone),
-        BlockId::StoneBrickStairs => Single(Stone),
+        BlockId::StoneBrickSlab => Slab { color: Stone },
+        BlockId::StoneBrickStairs => Stairs { color: Stone },
         BlockId::StoneBrickWall => Single(Stone),
         BlockId::StoneBricks => Single(Stone),
         BlockId::StoneButton => Single(None),
-        BlockId::StonePressurePlate => Single(Stone),
-        BlockId::StoneSlab => Single(Stone),
-        BlockId::StoneStairs => Single(Stone),
+        BlockId::StonePressurePlate => Thin { color: Stone },
+        BlockId::StoneSlab => Slab { color: Stone },
+        BlockId::StoneStairs => Stairs { color: Stone },
         BlockId::Stonecutter => Single(Stone),
         BlockId::StrippedAcaciaLog => Pillar { top: ColorOrange, side: ColorOrange },
         BlockId::StrippedAcaciaWood => Single(ColorOrange),
@@ -996,13 +997,13 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::StrippedWarpedStem => Single(WarpedStem),
         BlockId::StructureBlock => Single(ColorLightGray),
         BlockId::StructureVoid => Single(None),
-        BlockId::SugarCane => Single(Plant),
+        BlockId::SugarCane => Biome { color: Plant, kind: BiomeColorKind::Grass },
         BlockId::Sunflower => Single(Plant),
         BlockId::SuspiciousGravel => Single(Stone),
         BlockId::SuspiciousSand => Single(Sand),
         BlockId::SweetBerryBush => Single(Plant),
         BlockId::TallDryGrass => Single(ColorYellow),
-        BlockId::TallGrass => Single(Plant),
+        BlockId::TallGrass => Biome { color: Plant, kind: BiomeColorKind::Grass },
         BlockId::TallSeagrass => Single(Water),
         BlockId::Target => Single(Quartz),
         BlockId::Terracotta => Single(ColorOrange),
@@ -1022,19 +1023,19 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::TubeCoralFan => Single(ColorBlue),
         BlockId::TubeCoralWallFan => Single(ColorBlue),
         BlockId::Tuff => Single(TerracottaGray),
-        BlockId::TuffBrickSlab => Single(TerracottaGray),
-        BlockId::TuffBrickStairs => Single(TerracottaGray),
+        BlockId::TuffBrickSlab => Slab { color: TerracottaGray },
+        BlockId::TuffBrickStairs => Stairs { color: TerracottaGray },
         BlockId::TuffBrickWall => Single(TerracottaGray),
         BlockId::TuffBricks => Single(TerracottaGray),
-        BlockId::TuffSlab => Single(TerracottaGray),
-        BlockId::TuffStairs => Single(TerracottaGray),
+        BlockId::TuffSlab => Slab { color: TerracottaGray },
+        BlockId::TuffStairs => Stairs { color: TerracottaGray },
         BlockId::TuffWall => Single(TerracottaGray),
         BlockId::TurtleEgg => Single(Sand),
         BlockId::TwistingVines => Single(ColorCyan),
         BlockId::TwistingVinesPlant => Single(ColorCyan),
         BlockId::Vault => Single(Stone),
         BlockId::VerdantFroglight => Single(GlowLichen),
-        BlockId::Vine => Single(Plant),
+        BlockId::Vine => Biome { color: Plant, kind: BiomeColorKind::Foliage },
         BlockId::Air => Single(None),
         BlockId::WallTorch => Single(None),
         BlockId::WarpedButton => Single(None),
@@ -1046,18 +1047,18 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WarpedHyphae => Single(WarpedHyphae),
         BlockId::WarpedNylium => Single(WarpedNylium),
         BlockId::WarpedPlanks => Single(WarpedStem),
-        BlockId::WarpedPressurePlate => Single(WarpedStem),
+        BlockId::WarpedPressurePlate => Thin { color: WarpedStem },
         BlockId::WarpedRoots => Single(ColorCyan),
         BlockId::WarpedShelf => Single(WarpedStem),
         BlockId::WarpedSign => Single(WarpedStem),
-        BlockId::WarpedSlab => Single(WarpedStem),
-        BlockId::WarpedStairs => Single(WarpedStem),
+        BlockId::WarpedSlab => Slab { color: WarpedStem },
+        BlockId::WarpedStairs => Stairs { color: WarpedStem },
         BlockId::WarpedStem => Single(WarpedStem),
         BlockId::WarpedTrapdoor => Single(WarpedStem),
         BlockId::WarpedWallHangingSign => Single(WarpedStem),
         BlockId::WarpedWallSign => Single(WarpedStem),
         BlockId::WarpedWartBlock => Single(WarpedWartBlock),
-        BlockId::Water => Single(Water),
+        BlockId::Water => Biome { color: Water, kind: BiomeColorKind::Water },
         BlockId::WaterCauldron => Single(Stone),
         BlockId::WaxedChiseledCopper => Single(ColorOrange),
         BlockId::WaxedCopperBlock => Single(ColorOrange),
@@ -1068,8 +1069,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WaxedCopperGrate => Single(ColorOrange),
         BlockId::WaxedCopperTrapdoor => Single(ColorOrange),
         BlockId::WaxedCutCopper => Single(ColorOrange),
-        BlockId::WaxedCutCopperSlab => Single(ColorOrange),
-        BlockId::WaxedCutCopperStairs => Single(ColorOrange),
+        BlockId::WaxedCutCopperSlab => Slab { color: ColorOrange },
+        BlockId::WaxedCutCopperStairs => Stairs { color: ColorOrange },
         BlockId::WaxedExposedChiseledCopper => Single(TerracottaLightGray),
         BlockId::WaxedExposedCopper => Single(TerracottaLightGray),
         BlockId::WaxedExposedCopperBulb => Single(TerracottaLightGray),
@@ -1079,8 +1080,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WaxedExposedCopperGrate => Single(TerracottaLightGray),
         BlockId::WaxedExposedCopperTrapdoor => Single(TerracottaLightGray),
         BlockId::WaxedExposedCutCopper => Single(TerracottaLightGray),
-        BlockId::WaxedExposedCutCopperSlab => Single(TerracottaLightGray),
-        BlockId::WaxedExposedCutCopperStairs => Single(TerracottaLightGray),
+        BlockId::WaxedExposedCutCopperSlab => Slab { color: TerracottaLightGray },
+        BlockId::WaxedExposedCutCopperStairs => Stairs { color: TerracottaLightGray },
         BlockId::WaxedExposedLightningRod => Single(TerracottaLightGray),
         BlockId::WaxedLightningRod => Single(ColorOrange),
         BlockId::WaxedOxidizedChiseledCopper => Single(WarpedNylium),
@@ -1092,8 +1093,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WaxedOxidizedCopperGrate => Single(WarpedNylium),
         BlockId::WaxedOxidizedCopperTrapdoor => Single(WarpedNylium),
         BlockId::WaxedOxidizedCutCopper => Single(WarpedNylium),
-        BlockId::WaxedOxidizedCutCopperSlab => Single(WarpedNylium),
-        BlockId::WaxedOxidizedCutCopperStairs => Single(WarpedNylium),
+        BlockId::WaxedOxidizedCutCopperSlab => Slab { color: WarpedNylium },
+        BlockId::WaxedOxidizedCutCopperStairs => Stairs { color: WarpedNylium },
         BlockId::WaxedOxidizedLightningRod => Single(WarpedNylium),
         BlockId::WaxedWeatheredChiseledCopper => Single(WarpedStem),
         BlockId::WaxedWeatheredCopper => Single(WarpedStem),
@@ -1104,8 +1105,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WaxedWeatheredCopperGrate => Single(WarpedStem),
         BlockId::WaxedWeatheredCopperTrapdoor => Single(WarpedStem),
         BlockId::WaxedWeatheredCutCopper => Single(WarpedStem),
-        BlockId::WaxedWeatheredCutCopperSlab => Single(WarpedStem),
-        BlockId::WaxedWeatheredCutCopperStairs => Single(WarpedStem),
+        BlockId::WaxedWeatheredCutCopperSlab => Slab { color: WarpedStem },
+        BlockId::WaxedWeatheredCutCopperStairs => Stairs { color: WarpedStem },
         BlockId::WaxedWeatheredLightningRod => Single(WarpedStem),
         BlockId::WeatheredChiseledCopper => Single(WarpedStem),
         BlockId::WeatheredCopper => Single(WarpedStem),
@@ -1116,8 +1117,8 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WeatheredCopperGrate => Single(WarpedStem),
         BlockId::WeatheredCopperTrapdoor => Single(WarpedStem),
         BlockId::WeatheredCutCopper => Single(WarpedStem),
-        BlockId::WeatheredCutCopperSlab => Single(WarpedStem),
-        BlockId::WeatheredCutCopperStairs => Single(WarpedStem),
+        BlockId::WeatheredCutCopperSlab => Slab { color: WarpedStem },
+        BlockId::WeatheredCutCopperStairs => Stairs { color: WarpedStem },
         BlockId::WeatheredLightningRod => Single(WarpedStem),
         BlockId::WeepingVines => Single(Nether),
         BlockId::WeepingVinesPlant => Single(Nether),
@@ -1127,7 +1128,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::WhiteBed => Bed { head: Wool, foot: Snow },
         BlockId::WhiteCandle => Single(Wool),
         BlockId::WhiteCandleCake => Single(None),
-        BlockId::WhiteCarpet => Single(Snow),
+        BlockId::WhiteCarpet => Thin { color: Snow },
         BlockId::WhiteConcrete => Single(Snow),
         BlockId::WhiteConcretePowder => Single(Snow),
         BlockId::WhiteGlazedTerracotta => Single(Snow),
@@ -1146,7 +1147,7 @@ pub(crate) fn get_block_colors() -> HashMap<BlockId, crate::BlockMapColor> {
         BlockId::YellowBed => Bed { head: Wool, foot: ColorYellow },
         BlockId::YellowCandle => Single(ColorYellow),
         BlockId::YellowCandleCake => Single(None),
-        BlockId::YellowCarpet => Single(ColorYellow),
+        BlockId::YellowCarpet => Thin { color: ColorYellow },
         BlockId::YellowConcrete => Single(ColorYellow),
         BlockId::YellowConcretePowder => Single(ColorYellow),
         BlockId::YellowGlazedTerracotta => Single(ColorYellow),
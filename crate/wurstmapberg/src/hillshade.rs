@@ -0,0 +1,36 @@
+use {
+    image::Rgba,
+    crate::Tint,
+};
+
+/// The position of the light for [`Renderer::hillshade`](crate::Renderer::hillshade).
+#[derive(Debug, Clone, Copy)]
+pub struct Hillshade {
+    /// The compass direction the light comes from, in degrees clockwise from north.
+    pub azimuth: f64,
+    /// The angle of the light above the horizon, in degrees.
+    pub altitude: f64,
+}
+
+impl Default for Hillshade {
+    /// Light from the northwest at 45°, the usual default for relief maps.
+    fn default() -> Self {
+        Self {
+            azimuth: 315.0,
+            altitude: 45.0,
+        }
+    }
+}
+
+impl Hillshade {
+    /// Shades an untinted pixel based on the slope of the surface, given as the height differences to its north and west neighbors in blocks. Flat surfaces are drawn at [`Tint::Normal`] brightness.
+    pub(crate) fn shade(&self, Rgba([r, g, b, a]): Rgba<u8>, north_difference: f64, west_difference: f64) -> Rgba<u8> {
+        let azimuth = self.azimuth.to_radians();
+        let altitude = self.altitude.to_radians();
+        // cosine of the angle between the surface normal (-west_difference, 1, -north_difference) and the direction towards the light, with X pointing east, Y up, and Z south
+        let illumination = (altitude.sin() - west_difference * azimuth.sin() * altitude.cos() + north_difference * azimuth.cos() * altitude.cos()) / (west_difference.powi(2) + north_difference.powi(2) + 1.0).sqrt();
+        let multiplier = (f64::from(Tint::Normal.multiplier()) * illumination.max(0.0) / altitude.sin()).min(255.0);
+        let [r, g, b] = [r, g, b].map(|channel| (f64::from(channel) * multiplier / 255.0) as u8);
+        Rgba([r, g, b, a])
+    }
+}
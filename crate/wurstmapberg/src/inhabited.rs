@@ -0,0 +1,42 @@
+use {
+    image::{
+        Rgba,
+        RgbaImage,
+    },
+    mcanvil::Region,
+    crate::Error,
+};
+
+/// The inhabited time at which local difficulty stops increasing, in ticks (50 hours).
+const MAX_INHABITED_TIME: i64 = 50 * 60 * 60 * 20;
+
+/// Maps an inhabited time to a color ramp from blue over green and yellow to red. The scale is logarithmic since most chunks are only passed through briefly.
+fn ramp(inhabited_time: i64) -> Rgba<u8> {
+    let fraction = ((inhabited_time.max(0) as f64).ln_1p() / (MAX_INHABITED_TIME as f64).ln_1p()).min(1.0);
+    let [r, g, b] = match fraction {
+        ..0.25 => [0.0, fraction * 4.0, 1.0],
+        ..0.5 => [0.0, 1.0, 1.0 - (fraction - 0.25) * 4.0],
+        ..0.75 => [(fraction - 0.5) * 4.0, 1.0, 0.0],
+        _ => [1.0, 1.0 - (fraction - 0.75) * 4.0, 0.0],
+    }.map(|channel| (channel * 255.0).round() as u8);
+    Rgba([r, g, b, u8::MAX])
+}
+
+/// Draws each chunk of the region in a color representing how long players have spent in it. Chunks that were never inhabited or aren't generated are transparent.
+pub fn render_inhabited_time(region: &Region) -> Result<RgbaImage, Error> {
+    let [region_x, region_z] = region.coords;
+    let mut img = RgbaImage::new(16 * 32, 16 * 32);
+    for chunk_z in 0..32 {
+        for chunk_x in 0..32 {
+            let Some(col) = region.chunk_column([region_x * 32 + chunk_x, region_z * 32 + chunk_z])? else { continue };
+            if col.inhabited_time <= 0 { continue }
+            let color = ramp(col.inhabited_time);
+            for block_z in 0..16 {
+                for block_x in 0..16 {
+                    img[(chunk_x as u32 * 16 + block_x, chunk_z as u32 * 16 + block_z)] = color;
+                }
+            }
+        }
+    }
+    Ok(img)
+}
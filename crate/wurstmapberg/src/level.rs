@@ -0,0 +1,81 @@
+use {
+    std::path::Path,
+    serde::Deserialize,
+    crate::Error,
+};
+
+#[derive(Deserialize)]
+struct LevelDat {
+    #[serde(rename = "Data")]
+    data: LevelData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LevelData {
+    level_name: String,
+    /// Present since Minecraft 1.16.
+    world_gen_settings: Option<WorldGenSettings>,
+    /// Used before Minecraft 1.16.
+    random_seed: Option<i64>,
+    /// Present since Minecraft 1.21.9.
+    #[serde(rename = "spawn")]
+    spawn: Option<Spawn>,
+    /// Used before Minecraft 1.21.9.
+    spawn_x: Option<i32>,
+    spawn_y: Option<i32>,
+    spawn_z: Option<i32>,
+    border_center_x: Option<f64>,
+    border_center_z: Option<f64>,
+    border_size: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct WorldGenSettings {
+    seed: i64,
+}
+
+#[derive(Deserialize)]
+struct Spawn {
+    pos: Vec<i32>,
+}
+
+/// The area of the world inside the world border.
+#[derive(Debug, Clone, Copy)]
+pub struct WorldBorder {
+    /// The center of the border as `[x, z]`.
+    pub center: [f64; 2],
+    /// The width and depth of the border, in blocks.
+    pub size: f64,
+}
+
+/// Information about a world from its `level.dat` file.
+#[derive(Debug, Clone)]
+pub struct Level {
+    pub name: String,
+    pub seed: i64,
+    /// The world spawn point as `[x, y, z]`, if present.
+    pub spawn: Option<[i32; 3]>,
+    pub border: Option<WorldBorder>,
+}
+
+impl Level {
+    /// Reads the `level.dat` file in the given world directory.
+    pub async fn read(world_dir: &Path) -> Result<Self, Error> {
+        let buf = tokio::fs::read(world_dir.join("level.dat")).await.map_err(Error::LevelDatIo)?;
+        let LevelDat { data } = nbt::from_gzip_reader(&*buf).map_err(Error::LevelDat)?;
+        Ok(Self {
+            seed: data.world_gen_settings.map(|settings| settings.seed).or(data.random_seed).ok_or(Error::MissingSeed)?,
+            spawn: match (data.spawn, data.spawn_x, data.spawn_y, data.spawn_z) {
+                (Some(Spawn { pos }), _, _, _) => <[i32; 3]>::try_from(pos).ok(),
+                (None, Some(x), Some(y), Some(z)) => Some([x, y, z]),
+                (None, _, _, _) => None,
+            },
+            border: data.border_size.map(|size| WorldBorder {
+                center: [data.border_center_x.unwrap_or_default(), data.border_center_z.unwrap_or_default()],
+                size,
+            }),
+            name: data.level_name,
+        })
+    }
+}
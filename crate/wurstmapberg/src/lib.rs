@@ -0,0 +1,1380 @@
+//! Renders Minecraft worlds into top-down maps, one image per region file.
+
+use {
+    std::{
+        collections::{
+            BTreeMap,
+            BTreeSet,
+            HashMap,
+        },
+        io,
+        num::NonZero,
+        path::{
+            Path,
+            PathBuf,
+        },
+        sync::Arc,
+    },
+    futures::stream::{
+        Stream,
+        TryStreamExt as _,
+    },
+    image::{
+        Pixel as _,
+        Rgba,
+        RgbaImage,
+    },
+    mcanvil::{
+        BlockId,
+        BlockState,
+        ChunkColumn,
+        ChunkColumnDecodeError,
+        Dimension,
+        Region,
+        RegionDecodeError,
+    },
+    rayon::prelude::*,
+    serde::Deserialize,
+};
+pub use crate::{
+    biome::BiomeColorKind,
+    census::count_blocks,
+    hillshade::Hillshade,
+    inhabited::render_inhabited_time,
+    level::{
+        Level,
+        WorldBorder,
+    },
+    light::LightKind,
+    maps::MapItem,
+    palette::{
+        Palette,
+        get_block_colors,
+        uncovered_blocks,
+    },
+    players::Player,
+    status::render_chunk_status,
+    validate::validate_region,
+};
+
+#[cfg(test)] use criterion as _; // only used in benchmarks
+
+mod biome;
+mod census;
+mod colors;
+mod hillshade;
+mod inhabited;
+mod level;
+mod light;
+mod maps;
+mod markers;
+mod palette;
+mod players;
+mod slime;
+mod status;
+mod validate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MapColor {
+    None,
+    Grass,
+    Sand,
+    Wool,
+    Fire,
+    Ice,
+    Metal,
+    Plant,
+    Snow,
+    Clay,
+    Dirt,
+    Stone,
+    Water,
+    Wood,
+    Quartz,
+    ColorOrange,
+    ColorMagenta,
+    ColorLightBlue,
+    ColorYellow,
+    ColorLightGreen,
+    ColorPink,
+    ColorGray,
+    ColorLightGray,
+    ColorCyan,
+    ColorPurple,
+    ColorBlue,
+    ColorBrown,
+    ColorGreen,
+    ColorRed,
+    ColorBlack,
+    Gold,
+    Diamond,
+    Lapis,
+    Emerald,
+    Podzol,
+    Nether,
+    TerracottaWhite,
+    TerracottaOrange,
+    TerracottaMagenta,
+    TerracottaLightBlue,
+    TerracottaYellow,
+    TerracottaLightGreen,
+    TerracottaPink,
+    TerracottaGray,
+    TerracottaLightGray,
+    TerracottaCyan,
+    TerracottaPurple,
+    TerracottaBlue,
+    TerracottaBrown,
+    TerracottaGreen,
+    TerracottaRed,
+    TerracottaBlack,
+    CrimsonNylium,
+    CrimsonStem,
+    CrimsonHyphae,
+    WarpedNylium,
+    WarpedStem,
+    WarpedHyphae,
+    WarpedWartBlock,
+    Deepslate,
+    RawIron,
+    GlowLichen,
+}
+
+/// One of the four brightness levels a map color can be drawn at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tint {
+    /// The fourth brightness level of map colors, which vanilla maps don't use for terrain.
+    Darkest,
+    Dark,
+    Normal,
+    /// The untinted base color.
+    Light,
+}
+
+impl Tint {
+    pub const ALL: [Self; 4] = [Self::Darkest, Self::Dark, Self::Normal, Self::Light];
+
+    /// The brightness level for the shade part of a color byte in an in-game map item. Only the lowest two bits are used.
+    pub fn from_shade(shade: u8) -> Self {
+        match shade % 4 {
+            0 => Self::Dark,
+            1 => Self::Normal,
+            2 => Self::Light,
+            _ => Self::Darkest,
+        }
+    }
+
+    /// The shade part of a color byte in an in-game map item for this brightness level. Inverse of [`Tint::from_shade`].
+    pub fn shade(&self) -> u8 {
+        match self {
+            Self::Dark => 0,
+            Self::Normal => 1,
+            Self::Light => 2,
+            Self::Darkest => 3,
+        }
+    }
+
+    fn multiplier(&self) -> u16 {
+        match self {
+            Self::Darkest => 135,
+            Self::Dark => 180,
+            Self::Normal => 220,
+            Self::Light => 255,
+        }
+    }
+
+    fn apply(&self, base_rgb: u32) -> Rgba<u8> {
+        scale_rgb(base_rgb, self.multiplier())
+    }
+
+    /// Applies this tint on top of an already tinted pixel, e.g. for contour lines.
+    fn darken(&self, Rgba([r, g, b, a]): Rgba<u8>) -> Rgba<u8> {
+        let [r, g, b] = [r, g, b].map(|channel| (u16::from(channel) * self.multiplier() / 255) as u8);
+        Rgba([r, g, b, a])
+    }
+}
+
+fn scale_rgb(base_rgb: u32, multiplier: u16) -> Rgba<u8> {
+    let [_, r, g, b] = base_rgb.to_be_bytes().map(|channel| (u16::from(channel) * multiplier / 255) as u8);
+    Rgba([r, g, b, u8::MAX])
+}
+
+/// The brightness of each [`Tint`] relative to the base color, out of 255. The default values are the ones used by Minecraft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TintMultipliers {
+    pub darkest: u8,
+    pub dark: u8,
+    pub normal: u8,
+    pub light: u8,
+}
+
+impl Default for TintMultipliers {
+    fn default() -> Self {
+        Self {
+            darkest: Tint::Darkest.multiplier() as u8,
+            dark: Tint::Dark.multiplier() as u8,
+            normal: Tint::Normal.multiplier() as u8,
+            light: Tint::Light.multiplier() as u8,
+        }
+    }
+}
+
+impl TintMultipliers {
+    fn apply(&self, tint: Tint, base_rgb: u32) -> Rgba<u8> {
+        scale_rgb(base_rgb, u16::from(match tint {
+            Tint::Darkest => self.darkest,
+            Tint::Dark => self.dark,
+            Tint::Normal => self.normal,
+            Tint::Light => self.light,
+        }))
+    }
+}
+
+impl MapColor {
+    /// Every map color, in the order of their IDs in Minecraft.
+    pub const ALL: [Self; 62] = [
+        Self::None,
+        Self::Grass,
+        Self::Sand,
+        Self::Wool,
+        Self::Fire,
+        Self::Ice,
+        Self::Metal,
+        Self::Plant,
+        Self::Snow,
+        Self::Clay,
+        Self::Dirt,
+        Self::Stone,
+        Self::Water,
+        Self::Wood,
+        Self::Quartz,
+        Self::ColorOrange,
+        Self::ColorMagenta,
+        Self::ColorLightBlue,
+        Self::ColorYellow,
+        Self::ColorLightGreen,
+        Self::ColorPink,
+        Self::ColorGray,
+        Self::ColorLightGray,
+        Self::ColorCyan,
+        Self::ColorPurple,
+        Self::ColorBlue,
+        Self::ColorBrown,
+        Self::ColorGreen,
+        Self::ColorRed,
+        Self::ColorBlack,
+        Self::Gold,
+        Self::Diamond,
+        Self::Lapis,
+        Self::Emerald,
+        Self::Podzol,
+        Self::Nether,
+        Self::TerracottaWhite,
+        Self::TerracottaOrange,
+        Self::TerracottaMagenta,
+        Self::TerracottaLightBlue,
+        Self::TerracottaYellow,
+        Self::TerracottaLightGreen,
+        Self::TerracottaPink,
+        Self::TerracottaGray,
+        Self::TerracottaLightGray,
+        Self::TerracottaCyan,
+        Self::TerracottaPurple,
+        Self::TerracottaBlue,
+        Self::TerracottaBrown,
+        Self::TerracottaGreen,
+        Self::TerracottaRed,
+        Self::TerracottaBlack,
+        Self::CrimsonNylium,
+        Self::CrimsonStem,
+        Self::CrimsonHyphae,
+        Self::WarpedNylium,
+        Self::WarpedStem,
+        Self::WarpedHyphae,
+        Self::WarpedWartBlock,
+        Self::Deepslate,
+        Self::RawIron,
+        Self::GlowLichen,
+    ];
+
+    /// The untinted color as `0xRRGGBB`, or `None` for [`MapColor::None`], which is transparent.
+    pub fn base_rgb(&self) -> Option<u32> {
+        Some(match self {
+            MapColor::None => return None,
+            MapColor::Grass => 8368696_u32,
+            MapColor::Sand => 16247203,
+            MapColor::Wool => 13092807,
+            MapColor::Fire => 16711680,
+            MapColor::Ice => 10526975,
+            MapColor::Metal => 10987431,
+            MapColor::Plant => 31744,
+            MapColor::Snow => 16777215,
+            MapColor::Clay => 10791096,
+            MapColor::Dirt => 9923917,
+            MapColor::Stone => 7368816,
+            MapColor::Water => 4210943,
+            MapColor::Wood => 9402184,
+            MapColor::Quartz => 16776437,
+            MapColor::ColorOrange => 14188339,
+            MapColor::ColorMagenta => 11685080,
+            MapColor::ColorLightBlue => 6724056,
+            MapColor::ColorYellow => 15066419,
+            MapColor::ColorLightGreen => 8375321,
+            MapColor::ColorPink => 15892389,
+            MapColor::ColorGray => 5000268,
+            MapColor::ColorLightGray => 10066329,
+            MapColor::ColorCyan => 5013401,
+            MapColor::ColorPurple => 8339378,
+            MapColor::ColorBlue => 3361970,
+            MapColor::ColorBrown => 6704179,
+            MapColor::ColorGreen => 6717235,
+            MapColor::ColorRed => 10040115,
+            MapColor::ColorBlack => 1644825,
+            MapColor::Gold => 16445005,
+            MapColor::Diamond => 6085589,
+            MapColor::Lapis => 4882687,
+            MapColor::Emerald => 55610,
+            MapColor::Podzol => 8476209,
+            MapColor::Nether => 7340544,
+            MapColor::TerracottaWhite => 13742497,
+            MapColor::TerracottaOrange => 10441252,
+            MapColor::TerracottaMagenta => 9787244,
+            MapColor::TerracottaLightBlue => 7367818,
+            MapColor::TerracottaYellow => 12223780,
+            MapColor::TerracottaLightGreen => 6780213,
+            MapColor::TerracottaPink => 10505550,
+            MapColor::TerracottaGray => 3746083,
+            MapColor::TerracottaLightGray => 8874850,
+            MapColor::TerracottaCyan => 5725276,
+            MapColor::TerracottaPurple => 8014168,
+            MapColor::TerracottaBlue => 4996700,
+            MapColor::TerracottaBrown => 4993571,
+            MapColor::TerracottaGreen => 5001770,
+            MapColor::TerracottaRed => 9321518,
+            MapColor::TerracottaBlack => 2430480,
+            MapColor::CrimsonNylium => 12398641,
+            MapColor::CrimsonStem => 9715553,
+            MapColor::CrimsonHyphae => 6035741,
+            MapColor::WarpedNylium => 1474182,
+            MapColor::WarpedStem => 3837580,
+            MapColor::WarpedHyphae => 5647422,
+            MapColor::WarpedWartBlock => 1356933,
+            MapColor::Deepslate => 6579300,
+            MapColor::RawIron => 14200723,
+            MapColor::GlowLichen => 8365974,
+        })
+    }
+
+    /// The map color with the given ID, as used in Minecraft's map item data.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(id)).copied()
+    }
+
+    /// The ID of this map color in Minecraft. Inverse of [`MapColor::from_id`].
+    pub fn id(&self) -> u8 {
+        // the variants are declared in ID order
+        *self as u8
+    }
+
+    /// Decodes a color byte as stored in an in-game map item, which is the map color ID times 4 plus the shade. Returns `None` for unknown map color IDs.
+    pub fn from_map_byte(byte: u8) -> Option<(Self, Tint)> {
+        Some((Self::from_id(byte / 4)?, Tint::from_shade(byte % 4)))
+    }
+
+    /// Encodes this map color at the given brightness level as a color byte like in an in-game map item. Inverse of [`MapColor::from_map_byte`].
+    pub fn to_map_byte(&self, tint: Tint) -> u8 {
+        self.id() * 4 + tint.shade()
+    }
+
+    /// The color of a pixel of this map color at the given brightness level. [`MapColor::None`] is fully transparent.
+    pub fn tint(&self, tint: Tint) -> Rgba<u8> {
+        self.base_rgb().map_or(Rgba([0; 4]), |base_rgb| tint.apply(base_rgb))
+    }
+
+    /// Recognizes a pixel drawn by [`MapColor::tint`]. Returns `None` for transparent pixels and for colors that aren't a tinted map color, e.g. biome-dependent colors or markers.
+    pub fn from_rgb(pixel: Rgba<u8>) -> Option<(Self, Tint)> {
+        if pixel[3] == 0 { return None }
+        Self::ALL.into_iter()
+            .filter(|color| *color != Self::None)
+            .flat_map(|color| Tint::ALL.map(|tint| (color, tint)))
+            .find(|&(color, tint)| color.tint(tint) == pixel)
+    }
+}
+
+/// The palette file passed via `--colors` uses serde's default externally tagged representation of this type, e.g. `{"minecraft:red_bed": {"Bed": {"head": "Wool", "foot": "ColorRed"}}}`.
+#[derive(Debug, Clone, Deserialize)]
+pub enum BlockMapColor {
+    Single(MapColor),
+    Bed {
+        head: MapColor,
+        foot: MapColor,
+    },
+    Crops {
+        growing: MapColor,
+        grown: MapColor,
+    },
+    Pillar {
+        top: MapColor,
+        side: MapColor,
+    },
+    Waterloggable {
+        dry: MapColor,
+        wet: MapColor,
+    },
+    /// Like `Single`, but bottom slabs count as half a block for shading.
+    Slab {
+        color: MapColor,
+    },
+    /// Like `Single`, but bottom stairs count as half a block for shading.
+    Stairs {
+        color: MapColor,
+    },
+    /// Like `Single`, but for blocks like carpets that are too thin to affect shading, so the height of the block below is used instead.
+    Thin {
+        color: MapColor,
+    },
+    /// Like `Single`, but for snow, which is as high as its number of layers for shading, so a few layers take their height from the block below like `Thin` blocks.
+    Layers {
+        color: MapColor,
+    },
+    /// Like `Single`, but tinted according to the biome the block is in.
+    Biome {
+        color: MapColor,
+        kind: BiomeColorKind,
+    },
+    /// Chosen by the value of a single block state property, e.g. `{"ByProperty": {"property": "open", "cases": [["true", "Wood"]], "default": "Stone"}}`. Blocks without the property or with a value that has no case get the default.
+    ByProperty {
+        property: String,
+        cases: Vec<(String, MapColor)>,
+        default: MapColor,
+    },
+}
+
+/// Used for waterlogged blocks, which show the water they contain.
+static WATERLOGGED_COLOR: BlockMapColor = BlockMapColor::Biome { color: MapColor::Water, kind: BiomeColorKind::Water };
+
+/// Technical blocks that are skipped when scanning for the surface, as if they were air. Barriers and light blocks only count if they're dry, since waterlogged ones show their water.
+fn is_non_occluding(block: &BlockState) -> bool {
+    match block.name {
+        BlockId::MovingPiston | BlockId::StructureVoid => true,
+        BlockId::Barrier | BlockId::Light => block.properties.get("waterlogged").is_none_or(|waterlogged| waterlogged != "true"),
+        _ => false,
+    }
+}
+
+/// Blocks that are drawn as a translucent layer over the block below them if transparent blocks are enabled.
+fn is_see_through(block: &BlockState) -> bool {
+    matches!(block.name,
+        BlockId::Ice
+        | BlockId::WhiteStainedGlass | BlockId::OrangeStainedGlass | BlockId::MagentaStainedGlass | BlockId::LightBlueStainedGlass
+        | BlockId::YellowStainedGlass | BlockId::LimeStainedGlass | BlockId::PinkStainedGlass | BlockId::GrayStainedGlass
+        | BlockId::LightGrayStainedGlass | BlockId::CyanStainedGlass | BlockId::PurpleStainedGlass | BlockId::BlueStainedGlass
+        | BlockId::BrownStainedGlass | BlockId::GreenStainedGlass | BlockId::RedStainedGlass | BlockId::BlackStainedGlass
+    )
+}
+
+fn resolve_color(color: &BlockMapColor, block: &BlockState) -> MapColor {
+    match *color {
+        BlockMapColor::Single(color) | BlockMapColor::Slab { color } | BlockMapColor::Stairs { color } | BlockMapColor::Thin { color } | BlockMapColor::Layers { color } | BlockMapColor::Biome { color, .. } => color,
+        BlockMapColor::Bed { head, foot } => if block.properties.get("part").is_some_and(|part| part == "head") { head } else { foot },
+        BlockMapColor::Crops { growing, grown } => if block.properties.get("age").is_some_and(|age| age == "7") { grown } else { growing },
+        BlockMapColor::Pillar { top, side } => if block.properties.get("axis").is_some_and(|axis| axis != "y") { side } else { top },
+        BlockMapColor::Waterloggable { dry, wet } => if block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") { wet } else { dry },
+        BlockMapColor::ByProperty { ref property, ref cases, default } => block.properties.get(property.as_str())
+            .and_then(|value| cases.iter().find(|(case, _)| case == value))
+            .map_or(default, |&(_, color)| color),
+    }
+}
+
+/// The height of the top of the given block above its Y coordinate, in half blocks.
+fn surface_offset(color: &BlockMapColor, block: &BlockState) -> i32 {
+    match color {
+        BlockMapColor::Slab { .. } => if block.properties.get("type").is_some_and(|slab_type| slab_type == "bottom") { 1 } else { 2 },
+        BlockMapColor::Stairs { .. } => if block.properties.get("half").is_some_and(|half| half == "bottom") { 1 } else { 2 },
+        // 8 layers make a full block, rounded down to half blocks
+        BlockMapColor::Layers { .. } => block.properties.get("layers").and_then(|layers| layers.parse::<i32>().ok()).map_or(2, |layers| layers.clamp(1, 8) / 4),
+        _ => 2,
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Surface {
+    y: i32,
+    color: MapColor,
+    /// The height of the top of the surface block, in half blocks. Used for shading.
+    height: i32,
+    /// The base color to use instead of that of the map color, e.g. adjusted for the biome if this is a biome-dependent block.
+    rgb: Option<u32>,
+    /// The highest light level emitted by the surface block or any block above it that was scanned past. Only computed if light sources are highlighted.
+    light: u8,
+    /// The untinted color of the highest surface above this one, if it was cut away. Only computed in Nether cutaway mode.
+    overhang: Option<Rgba<u8>>,
+    /// The untinted color of the topmost see-through block above this one, if any. Only computed if transparent blocks are enabled.
+    cover: Option<Rgba<u8>>,
+    /// Whether the surface block itself emits full-strength light, like lava or glowstone. Only computed if emissive blocks are enabled.
+    emissive: bool,
+    /// Whether the surface block is a thin block like a lily pad floating on water, in which case it's shaded by the depth of the water below it.
+    floating: bool,
+}
+
+/// Which of the heightmaps stored in each chunk column determines where to start scanning for the surface.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Heightmap {
+    #[default]
+    WorldSurface,
+    /// Ignores water and other fluids, for a bathymetric view.
+    OceanFloor,
+    MotionBlocking,
+    MotionBlockingNoLeaves,
+}
+
+impl Heightmap {
+    fn key(self) -> &'static str {
+        match self {
+            Self::WorldSurface => "WORLD_SURFACE",
+            Self::OceanFloor => "OCEAN_FLOOR",
+            Self::MotionBlocking => "MOTION_BLOCKING",
+            Self::MotionBlockingNoLeaves => "MOTION_BLOCKING_NO_LEAVES",
+        }
+    }
+
+    /// Computes the value of this heightmap for a block column from the block data, for chunk columns where it's missing. Which blocks block motion isn't known here, so the motion blocking heightmaps are approximated by the highest non-air block, ignoring leaves for [`Heightmap::MotionBlockingNoLeaves`].
+    fn compute(self, col: &ChunkColumn, block_x: usize, block_z: usize) -> i32 {
+        (col.y_pos..=column_top(col)).rev().find(|&y| {
+            let Some(chunk) = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)) else { return false };
+            let block = chunk.block_relative([block_x as u8, y.rem_euclid(16) as u8, block_z as u8]);
+            match self {
+                Self::WorldSurface | Self::MotionBlocking => block.name != BlockId::Air,
+                Self::OceanFloor => !matches!(block.name, BlockId::Air | BlockId::Water | BlockId::Lava | BlockId::BubbleColumn | BlockId::Seagrass | BlockId::TallSeagrass | BlockId::Kelp | BlockId::KelpPlant),
+                Self::MotionBlockingNoLeaves => !matches!(block.name, BlockId::Air | BlockId::OakLeaves | BlockId::SpruceLeaves | BlockId::BirchLeaves | BlockId::JungleLeaves | BlockId::AcaciaLeaves | BlockId::CherryLeaves | BlockId::DarkOakLeaves | BlockId::PaleOakLeaves | BlockId::MangroveLeaves | BlockId::AzaleaLeaves | BlockId::FloweringAzaleaLeaves),
+            }
+        }).map_or(col.y_pos, |y| y + 1)
+    }
+}
+
+/// The `DataVersion` of Minecraft 1.18, which introduced the current chunk format with the extended height range.
+const MIN_DATA_VERSION: i32 = 2860;
+
+/// The index of the chunk section containing the given Y coordinate, or `None` if it's outside of the range that can be stored in a chunk column.
+fn section_index(y: i32) -> Option<i8> {
+    i8::try_from(y.div_euclid(16)).ok()
+}
+
+/// The Y coordinate of the top layer of the highest section present in the given chunk column, or one below the bottom of the column if it has no sections. This is used instead of a fixed world height so worlds with custom height limits are handled correctly.
+fn column_top(col: &ChunkColumn) -> i32 {
+    (col.y_pos.div_euclid(16)..=i32::from(i8::MAX)).rev()
+        .find(|&chunk_y| i8::try_from(chunk_y).ok().and_then(|chunk_y| col.section_at(chunk_y)).is_some())
+        .map_or(col.y_pos - 1, |chunk_y| chunk_y * 16 + 15)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)] ChunkColumn(#[from] ChunkColumnDecodeError),
+    #[error(transparent)] Region(#[from] RegionDecodeError),
+    #[error(transparent)] Task(#[from] tokio::task::JoinError),
+    #[error("failed to decode block entities of chunk column {}, {}: {source}", chunk[0], chunk[1])]
+    BlockEntities {
+        chunk: [i32; 2],
+        source: nbt::Error,
+    },
+    #[error("failed to decode level.dat: {0}")]
+    LevelDat(nbt::Error),
+    #[error("failed to read level.dat: {0}")]
+    LevelDatIo(io::Error),
+    #[error("failed to decode map item at {}: {source}", path.display())]
+    MapData {
+        path: PathBuf,
+        source: nbt::Error,
+    },
+    #[error("map item at {} has invalid color data", path.display())]
+    MapDataColors {
+        path: PathBuf,
+    },
+    #[error("failed to read map item at {}: {source}", path.display())]
+    MapDataIo {
+        path: PathBuf,
+        source: io::Error,
+    },
+    #[error("level.dat does not contain the world seed")]
+    MissingSeed,
+    #[error("failed to decode player data at {}: {source}", path.display())]
+    PlayerData {
+        path: PathBuf,
+        source: nbt::Error,
+    },
+    #[error("failed to read player data at {}: {source}", path.display())]
+    PlayerDataIo {
+        path: PathBuf,
+        source: io::Error,
+    },
+    #[error("player data at {} has an invalid position", path.display())]
+    PlayerDataPos {
+        path: PathBuf,
+    },
+    #[error("region file not found")]
+    RegionNotFound,
+    #[error("chunk column {}, {} was last saved by Minecraft DataVersion {data_version}, but only chunk columns saved by Minecraft 1.18 or later are supported", chunk[0], chunk[1])]
+    UnsupportedDataVersion {
+        chunk: [i32; 2],
+        data_version: i32,
+    },
+}
+
+/// A rendered region, along with the IDs of any blocks that were skipped because they're missing from the color table.
+pub struct RenderedRegion {
+    pub img: RgbaImage,
+    pub unknown_blocks: BTreeSet<String>,
+    /// The highest `DataVersion` of the region's chunk columns, i.e. the Minecraft version that last saved it, if it has any chunk columns.
+    pub data_version: Option<i32>,
+    pub south_edge: SouthEdge,
+    /// How often each block was scanned, if [`Renderer::block_census`] is enabled.
+    pub block_counts: BTreeMap<String, u64>,
+    /// The number of chunk columns in the region that have been generated and were rendered.
+    pub columns: usize,
+    /// How many of those chunk columns are missing the selected [`Heightmap`], so it was recomputed from the block data. Always 0 in dimensions with a ceiling and with [`Renderer::y_level`].
+    pub fallback_columns: usize,
+}
+
+/// The surfaces of the southernmost row of blocks of a region. This is all that's needed from a region to shade its south neighbor, so it can be kept around instead of the entire region.
+#[derive(Clone)]
+pub struct SouthEdge {
+    coords: [i32; 2],
+    surfaces: Box<[Option<Surface>; 16 * 32]>,
+}
+
+impl SouthEdge {
+    /// The coordinates of the region this is the south edge of.
+    pub fn coords(&self) -> [i32; 2] {
+        self.coords
+    }
+}
+
+/// The surfaces of the easternmost column of blocks of a region, for shading its east neighbor if [`Renderer::west_shading`] is enabled.
+#[derive(Clone)]
+pub struct EastEdge {
+    coords: [i32; 2],
+    surfaces: Box<[Option<Surface>; 16 * 32]>,
+}
+
+impl EastEdge {
+    /// The coordinates of the region this is the east edge of.
+    pub fn coords(&self) -> [i32; 2] {
+        self.coords
+    }
+}
+
+/// Settings shared by all regions of a dimension.
+pub struct Renderer {
+    pub block_colors: Arc<HashMap<BlockId, BlockMapColor>>,
+    pub dimension: Dimension,
+    pub heightmap: Heightmap,
+    /// Whether the dimension has a bedrock roof like the Nether, so columns are scanned starting below `nether_ceiling` instead of from the heightmap.
+    pub ceiling: bool,
+    /// In dimensions with a ceiling, columns are scanned starting at the first air gap below this Y coordinate.
+    pub nether_ceiling: i32,
+    /// If `true`, dimensions with a ceiling are rendered down to the lowest floor below the ceiling cutoff, with the highest ledge or overhang above it drawn translucently on top.
+    pub nether_cutaway: bool,
+    /// If set, each column is rendered starting at this Y coordinate instead of the surface, ignoring any blocks above it.
+    pub y_level: Option<i32>,
+    /// If set, blocks above this Y coordinate are ignored, e.g. to render the terrain below floating builds.
+    pub max_y: Option<i32>,
+    /// If set, blocks below this Y coordinate are ignored, so deep holes like quarries are left transparent instead of showing what's at the bottom.
+    pub min_y: Option<i32>,
+    /// The number of layers of blocks with a map color to skip before picking a block, e.g. 1 to see the floors of caves below the surface.
+    pub cave_layers: u32,
+    /// If set, contour lines are drawn at multiples of this many blocks of elevation.
+    pub contours: Option<NonZero<u16>>,
+    /// If set, drops of at least this many blocks compared to the north neighbor are shaded darker than smaller drops.
+    pub steep_shading: Option<u16>,
+    /// The brightness of each level of shading. Markers and overlays always use the vanilla values.
+    pub tint_multipliers: TintMultipliers,
+    /// If `true`, the height difference to the west neighbor is added to the one to the north neighbor when shading. This gives a stronger relief effect but doesn't match the in-game map.
+    pub west_shading: bool,
+    /// If set, blocks other than water are shaded by the slope of the surface relative to a light source in this position, like a relief map, instead of like the in-game map. Overrides `steep_shading` and `west_shading`.
+    pub hillshade: Option<Hillshade>,
+    /// If set, blocks emitting at least this light level (or with a light source on top) are highlighted.
+    pub lights: Option<u8>,
+    /// If set to the world seed, chunks where slimes can spawn are tinted green. Ignored outside the Overworld.
+    pub slime_chunks: Option<i64>,
+    /// If set, a marker is drawn at this spawn point. Ignored outside the Overworld.
+    pub spawn_marker: Option<[i32; 3]>,
+    /// If set, this world border is outlined. Ignored outside the Overworld.
+    pub world_border: Option<WorldBorder>,
+    /// Markers are drawn for those of these players who are in this renderer's dimension.
+    pub players: Vec<Player>,
+    /// If `true`, banners placed in the world are marked like on in-game maps.
+    pub banners: bool,
+    /// If set, the structures (villages, strongholds, etc.) starting in this dimension whose IDs match any of these filters are marked. Filters without a namespace also match vanilla structures whose name starts with them, e.g. `village` for every type of village. An empty list marks all structures.
+    pub structures: Option<Vec<String>>,
+    /// If `true`, blocks missing from the color table are rendered in magenta instead of being skipped.
+    pub debug_unknown: bool,
+    /// If `true`, blocks that emit full-strength light, like lava, fire, and glowstone, are always drawn at full brightness regardless of shading.
+    pub emissive: bool,
+    /// If `true`, stained glass and ice are drawn as a translucent layer over the block below them instead of hiding it.
+    pub transparent_blocks: bool,
+    /// If `true`, the blocks scanned for each column, down to and including the rendered block, are counted. This is a census of the surface and what's above it, not of entire chunks.
+    pub block_census: bool,
+    /// If set, region and chunk boundaries are drawn on top of the map in this color.
+    pub grid: Option<Rgba<u8>>,
+}
+
+impl Renderer {
+    /// Creates a renderer with the default settings, matching the in-game map.
+    pub fn new(dimension: Dimension, block_colors: Arc<HashMap<BlockId, BlockMapColor>>) -> Self {
+        Self {
+            block_colors,
+            dimension,
+            heightmap: Heightmap::default(),
+            ceiling: dimension == Dimension::Nether,
+            nether_ceiling: 127,
+            nether_cutaway: false,
+            y_level: None,
+            max_y: None,
+            min_y: None,
+            cave_layers: 0,
+            contours: None,
+            steep_shading: None,
+            tint_multipliers: TintMultipliers::default(),
+            west_shading: false,
+            hillshade: None,
+            lights: None,
+            slime_chunks: None,
+            spawn_marker: None,
+            world_border: None,
+            players: Vec::default(),
+            banners: false,
+            structures: None,
+            debug_unknown: false,
+            emissive: false,
+            transparent_blocks: false,
+            block_census: false,
+            grid: None,
+        }
+    }
+
+    /// Returns the Y coordinate from which to scan down for the block to render.
+    fn scan_start(&self, col: &ChunkColumn, block_x: usize, block_z: usize) -> i32 {
+        let start = if let Some(y_level) = self.y_level {
+            y_level
+        } else if self.ceiling {
+            // there is no meaningful surface below the bedrock roof, so like the in-game map, start at the first air gap below the ceiling cutoff
+            self.air_below(col, block_x, self.nether_ceiling, block_z)
+        } else {
+            col.heightmaps.get(self.heightmap.key()).map_or_else(|| self.heightmap.compute(col, block_x, block_z), |heightmap| heightmap[block_z][block_x])
+        };
+        self.max_y.map_or(start, |max_y| start.min(max_y))
+    }
+
+    /// The lowest Y coordinate that's scanned in the given chunk column.
+    fn scan_bottom(&self, col: &ChunkColumn) -> i32 {
+        self.min_y.map_or(col.y_pos, |min_y| min_y.max(col.y_pos))
+    }
+
+    /// Returns the highest Y coordinate at or below `y` where the given block column is air, or one below the bottom of the scanned range if there is none.
+    fn air_below(&self, col: &ChunkColumn, block_x: usize, mut y: i32, block_z: usize) -> i32 {
+        while y >= self.scan_bottom(col) {
+            let block_y = y.rem_euclid(16) as usize;
+            let Some(chunk) = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)) else { return y };
+            if chunk.block_relative([block_x as u8, block_y as u8, block_z as u8]).name == BlockId::Air { return y }
+            y -= 1;
+        }
+        y
+    }
+
+    /// The height of the top of the given block, in half blocks.
+    fn block_height(&self, col: &ChunkColumn, block_x: usize, y: i32, block_z: usize) -> i32 {
+        let offset = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)).map_or(2, |chunk| {
+            let block = chunk.block_relative([block_x as u8, y.rem_euclid(16) as u8, block_z as u8]);
+            self.block_colors.get(&block.name).map_or(2, |color| surface_offset(color, &block))
+        });
+        2 * y + offset
+    }
+
+    /// Scans down the given block column, returning the first block with a map color below the configured number of cave layers.
+    fn find_surface(&self, unknown_blocks: &mut BTreeSet<String>, col: &ChunkColumn, block_x: usize, block_z: usize) -> Option<Surface> {
+        let top = self.find_surface_below(unknown_blocks, col, block_x, self.scan_start(col, block_x, block_z), block_z)?;
+        if !(self.nether_cutaway && self.ceiling) { return Some(top) }
+        // keep descending through the air gaps below, so multi-level caverns show their lowest floor
+        let mut floor = top;
+        loop {
+            let gap = self.air_below(col, block_x, floor.y - 1, block_z);
+            if gap < self.scan_bottom(col) { break }
+            let Some(lower) = self.find_surface_below(unknown_blocks, col, block_x, gap, block_z) else { break };
+            floor = lower;
+        }
+        if floor.y != top.y {
+            floor.overhang = Some(top.rgb.map_or_else(|| top.color.tint(Tint::Light), |rgb| Tint::Light.apply(rgb)));
+        }
+        Some(floor)
+    }
+
+    /// Like `find_surface`, but starts scanning at the given Y coordinate.
+    fn find_surface_below(&self, unknown_blocks: &mut BTreeSet<String>, col: &ChunkColumn, block_x: usize, start: i32, block_z: usize) -> Option<Surface> {
+        let mut cave_layers = self.cave_layers;
+        let mut in_layer = false;
+        let mut light = 0;
+        let mut cover = None;
+        // iterating over a range ensures we always make progress, even on blocks that are missing from the color table, and stops at the bottom of the world
+        (self.scan_bottom(col)..=start).rev().find_map(|y| {
+            let block_y = y.rem_euclid(16) as usize;
+            let found = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)).and_then(|chunk| {
+                let block = chunk.block_relative([block_x as u8, block_y as u8, block_z as u8]);
+                if self.lights.is_some() {
+                    light = light.max(light::light_level(&block));
+                }
+                // checked after light levels so invisible light blocks still count as light sources
+                if is_non_occluding(&block) { return None }
+                let Some(color) = self.block_colors.get(&block.name) else {
+                    unknown_blocks.insert(block.name.to_string());
+                    return self.debug_unknown.then_some((chunk, block, None, MapColor::ColorMagenta))
+                };
+                let map_color = resolve_color(color, &block);
+                (map_color != MapColor::None).then(|| (chunk, block, Some(color), map_color))
+            });
+            if cave_layers > 0 {
+                // a layer ends at the first block without a map color below it, e.g. the air of a cave
+                if found.is_some() {
+                    in_layer = true;
+                } else if in_layer {
+                    in_layer = false;
+                    cave_layers -= 1;
+                    // light sources above the floor being rendered don't light it
+                    light = 0;
+                }
+                return None
+            }
+            let (chunk, block, mut color, mut map_color) = found?;
+            if self.transparent_blocks && is_see_through(&block) {
+                // only the topmost of several layers is visible
+                cover.get_or_insert(map_color.tint(Tint::Light));
+                return None
+            }
+            if !matches!(color, Some(BlockMapColor::Waterloggable { .. })) && block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") {
+                // like on in-game maps, other waterlogged blocks show the water they contain, so they get the water depth tint
+                color = Some(&WATERLOGGED_COLOR);
+                map_color = MapColor::Water;
+            }
+            Some(Surface {
+                y,
+                color: map_color,
+                height: match color {
+                    // thin blocks take their height from the block they're placed on
+                    Some(BlockMapColor::Thin { .. }) => self.block_height(col, block_x, y - 1, block_z),
+                    // the upper halves of two block tall plants and doors take their height from the lower half, so they're shaded like a one block tall block
+                    _ if block.properties.get("half").is_some_and(|half| half == "upper") => self.block_height(col, block_x, y - 1, block_z),
+                    Some(color) => 2 * y + surface_offset(color, &block),
+                    None => 2 * y + 2,
+                },
+                rgb: match color {
+                    Some(BlockMapColor::Biome { kind, .. }) => {
+                        let biome = chunk.biome_relative([block_x as u8 / 4, block_y as u8 / 4, block_z as u8 / 4]);
+                        map_color.base_rgb().map(|base_rgb| kind.apply(base_rgb, &biome))
+                    }
+                    Some(_) => None,
+                    // a color that doesn't occur on normal maps, so coverage gaps stand out
+                    None => Some(0xff00ff),
+                },
+                light,
+                overhang: None,
+                cover,
+                emissive: self.emissive && light::light_level(&block) >= 15,
+                floating: matches!(color, Some(BlockMapColor::Thin { .. })) && self.water_depth(col, block_x, y - 1, block_z) > 0,
+            })
+        })
+    }
+
+    /// Counts the blocks of water from the given Y coordinate downward. Waterlogged blocks count as water, as does plain ice, so that frozen water is as deep as it would be unfrozen. Packed and blue ice share the ice map color but don't form on water, so they end the count.
+    fn water_depth(&self, col: &ChunkColumn, block_x: usize, y: i32, block_z: usize) -> usize {
+        (self.scan_bottom(col)..=y).rev().take_while(|y| {
+            let block_y = y.rem_euclid(16) as usize;
+            let Some(chunk) = section_index(*y).and_then(|chunk_y| col.section_at(chunk_y)) else { return false };
+            let block = chunk.block_relative([block_x as u8, block_y as u8, block_z as u8]);
+            if block.name == BlockId::Ice { return true }
+            let Some(color) = self.block_colors.get(&block.name) else { return false };
+            matches!(resolve_color(color, &block), MapColor::Water) || block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true")
+        }).count()
+    }
+
+    /// Whether rendering a region requires the [`EastEdge`] of its west neighbor for the best result.
+    pub fn needs_west_neighbor(&self) -> bool {
+        self.west_shading || self.hillshade.is_some() || self.contours.is_some()
+    }
+
+    /// Returns the surface directly north of the given block, or `None` if it's not on the map.
+    fn north_surface(&self, unknown_blocks: &mut BTreeSet<String>, region: &Region, prev: Option<&SouthEdge>, col: &ChunkColumn, block_x: usize, block_z: usize) -> Result<Option<Surface>, ChunkColumnDecodeError> {
+        Ok(if let Some(block_z) = block_z.checked_sub(1) {
+            // same chunk
+            self.find_surface(unknown_blocks, col, block_x, block_z)
+        } else if col.z_pos.rem_euclid(32) > 0 {
+            // same region
+            region.chunk_column([col.x_pos, col.z_pos - 1])?.and_then(|col| self.find_surface(unknown_blocks, &col, block_x, 15))
+        } else if let Some(prev) = prev {
+            // different region
+            prev.surfaces[col.x_pos.rem_euclid(32) as usize * 16 + block_x]
+        } else {
+            // not on map
+            None
+        })
+    }
+
+    /// Returns the surface directly west of the given block, or `None` if it's not on the map.
+    fn west_surface(&self, unknown_blocks: &mut BTreeSet<String>, region: &Region, west: Option<&EastEdge>, col: &ChunkColumn, block_x: usize, block_z: usize) -> Result<Option<Surface>, ChunkColumnDecodeError> {
+        Ok(if let Some(block_x) = block_x.checked_sub(1) {
+            // same chunk
+            self.find_surface(unknown_blocks, col, block_x, block_z)
+        } else if col.x_pos.rem_euclid(32) > 0 {
+            // same region
+            region.chunk_column([col.x_pos - 1, col.z_pos])?.and_then(|col| self.find_surface(unknown_blocks, &col, 15, block_z))
+        } else if let Some(west) = west {
+            // different region
+            west.surfaces[col.z_pos.rem_euclid(32) as usize * 16 + block_z]
+        } else {
+            // not on map, or the west neighbor wasn't loaded since it's only needed for contours and west shading
+            None
+        })
+    }
+
+    /// Renders the pixels of a chunk column, indexed as `[z][x]`, along with the surfaces of its southernmost row of blocks. `prev` and `west` are the edges of the regions directly north and west of `region`, if they were loaded.
+    fn render_column(&self, region: &Region, (prev, west): (Option<&SouthEdge>, Option<&EastEdge>), col: &ChunkColumn, unknown_blocks: &mut BTreeSet<String>, block_counts: &mut BTreeMap<String, u64>) -> Result<([[Rgba<u8>; 16]; 16], [Option<Surface>; 16]), ChunkColumnDecodeError> {
+        let mut pixels = [[Rgba([0; 4]); 16]; 16];
+        let mut south_row = [None; 16];
+        for block_z in 0..16 {
+            for block_x in 0..16 {
+                let surface = self.find_surface(unknown_blocks, col, block_x, block_z);
+                if block_z == 15 {
+                    south_row[block_x] = surface;
+                }
+                let Some(Surface { y, color: col_color, height, rgb, light, overhang, cover, emissive, floating }) = surface else { continue };
+                if self.block_census {
+                    for y in y..=self.scan_start(col, block_x, block_z) {
+                        let Some(chunk) = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)) else { continue };
+                        *block_counts.entry(chunk.block_relative([block_x as u8, y.rem_euclid(16) as u8, block_z as u8]).name.to_string()).or_default() += 1;
+                    }
+                }
+                // only needed for shading blocks other than water and for contour lines
+                let north_neighbor = if col_color == MapColor::Water && self.contours.is_none() {
+                    None
+                } else {
+                    self.north_surface(unknown_blocks, region, prev, col, block_x, block_z)?
+                };
+                // only needed for west shading or hillshading of blocks other than water and for contour lines
+                let west_neighbor = if self.contours.is_none() && (col_color == MapColor::Water || !self.west_shading && self.hillshade.is_none()) {
+                    None
+                } else {
+                    self.west_surface(unknown_blocks, region, west, col, block_x, block_z)?
+                };
+                let water_depth = match col_color {
+                    MapColor::Water => Some(self.water_depth(col, block_x, y, block_z)),
+                    // like on in-game maps, lily pads are shaded by the water below them
+                    _ if floating => Some(self.water_depth(col, block_x, y - 1, block_z)),
+                    _ => None,
+                };
+                let tint = match water_depth {
+                    // self-lit blocks ignore shading
+                    _ if emissive => Tint::Light,
+                    Some(water_depth) => match water_depth {
+                        ..=2 => Tint::Light,
+                        3..=4 => if (block_x + block_z) % 2 == 0 { Tint::Light } else { Tint::Normal },
+                        5..=6 => Tint::Normal,
+                        7..=9 => if (block_x + block_z) % 2 == 0 { Tint::Normal } else { Tint::Dark },
+                        _ => Tint::Dark,
+                    },
+                    // heights are in half blocks
+                    None => match height - north_neighbor.map_or(height, |north_neighbor| north_neighbor.height) + if self.west_shading { height - west_neighbor.map_or(height, |west_neighbor| west_neighbor.height) } else { 0 } {
+                        difference @ ..0 if self.steep_shading.is_some_and(|threshold| -difference >= 2 * i32::from(threshold)) => Tint::Darkest,
+                        ..0 => Tint::Dark,
+                        0 => Tint::Normal,
+                        1.. => Tint::Light,
+                    },
+                };
+                let mut pixel = match self.hillshade {
+                    Some(hillshade) if !emissive && water_depth.is_none() => {
+                        let base = match rgb {
+                            Some(rgb) => Tint::Light.apply(rgb),
+                            None => col_color.tint(Tint::Light),
+                        };
+                        // heights are in half blocks
+                        let difference = |neighbor: Option<Surface>| f64::from(height - neighbor.map_or(height, |neighbor| neighbor.height)) / 2.0;
+                        hillshade.shade(base, difference(north_neighbor), difference(west_neighbor))
+                    }
+                    _ => rgb.or_else(|| col_color.base_rgb()).map_or(Rgba([0; 4]), |rgb| self.tint_multipliers.apply(tint, rgb)),
+                };
+                if let Some(Rgba([r, g, b, _])) = cover {
+                    pixel.blend(&Rgba([r, g, b, 128]));
+                }
+                if let Some(interval) = self.contours {
+                    let interval = i32::from(interval.get());
+                    let crosses_contour = |neighbor: Option<Surface>| neighbor.is_some_and(|neighbor| neighbor.y.div_euclid(interval) != y.div_euclid(interval));
+                    if crosses_contour(north_neighbor) || crosses_contour(west_neighbor) {
+                        pixel = Tint::Dark.darken(pixel);
+                    }
+                }
+                if self.lights.is_some_and(|threshold| light >= threshold) {
+                    // brighter light sources glow more strongly
+                    pixel.blend(&Rgba([255, 224, 96, light.min(15) * 12]));
+                }
+                if let Some(Rgba([r, g, b, _])) = overhang {
+                    pixel.blend(&Rgba([r, g, b, 96]));
+                }
+                pixels[block_z][block_x] = pixel;
+            }
+        }
+        Ok((pixels, south_row))
+    }
+
+    /// Computes the south edge of a region, for shading its south neighbor, without rendering the rest of it.
+    pub fn south_edge(&self, region: &Region) -> Result<SouthEdge, Error> {
+        let [region_x, region_z] = region.coords;
+        // blocks missing from the color table are reported when the region itself is rendered
+        let mut unknown_blocks = BTreeSet::default();
+        let mut surfaces = Box::new([None; 16 * 32]);
+        for chunk_x in 0..32 {
+            let Some(col) = region.chunk_column([region_x * 32 + chunk_x, region_z * 32 + 31])? else { continue };
+            for block_x in 0..16 {
+                surfaces[chunk_x as usize * 16 + block_x] = self.find_surface(&mut unknown_blocks, &col, block_x, 15);
+            }
+        }
+        Ok(SouthEdge { coords: region.coords, surfaces })
+    }
+
+    /// Computes the east edge of a region, for shading its east neighbor if [`Renderer::west_shading`] is enabled, without rendering the rest of it.
+    pub fn east_edge(&self, region: &Region) -> Result<EastEdge, Error> {
+        let [region_x, region_z] = region.coords;
+        // blocks missing from the color table are reported when the region itself is rendered
+        let mut unknown_blocks = BTreeSet::default();
+        let mut surfaces = Box::new([None; 16 * 32]);
+        for chunk_z in 0..32 {
+            let Some(col) = region.chunk_column([region_x * 32 + 31, region_z * 32 + chunk_z])? else { continue };
+            for block_z in 0..16 {
+                surfaces[chunk_z as usize * 16 + block_z] = self.find_surface(&mut unknown_blocks, &col, 15, block_z);
+            }
+        }
+        Ok(EastEdge { coords: region.coords, surfaces })
+    }
+
+    /// Renders an already loaded region. `prev` is the south edge of the region directly north of `region`, if it exists. `west` is the east edge of the region directly west of `region`; it can be omitted unless [`Renderer::needs_west_neighbor`] returns `true`.
+    ///
+    /// This is CPU-bound, so async callers should run it on a blocking thread.
+    pub fn render(&self, region: &Region, prev: Option<&SouthEdge>, west: Option<&EastEdge>) -> Result<RenderedRegion, Error> {
+        let prev = prev.filter(|prev| prev.coords == [region.coords[0], region.coords[1] - 1]);
+        let west = west.filter(|west| west.coords == [region.coords[0] - 1, region.coords[1]]);
+        let [region_x, region_z] = region.coords;
+        let columns = (0..32 * 32).into_par_iter().map(|i| {
+            let Some(col) = region.chunk_column([region_x * 32 + i % 32, region_z * 32 + i / 32])? else { return Ok(None) };
+            if col.data_version < MIN_DATA_VERSION {
+                return Err(Error::UnsupportedDataVersion { chunk: [col.x_pos, col.z_pos], data_version: col.data_version })
+            }
+            let mut unknown_blocks = BTreeSet::default();
+            let mut block_counts = BTreeMap::default();
+            let (pixels, south_row) = self.render_column(region, (prev, west), &col, &mut unknown_blocks, &mut block_counts)?;
+            let banners = if self.banners { markers::banners(region, &col)? } else { BTreeMap::default() };
+            // structure starts are stored in the chunk where the structure begins, so the marker is drawn in its center
+            let structures = self.structures.as_ref().map(|filters| col.structures.starts.keys()
+                .filter(|id| markers::structure_selected(filters, id))
+                .map(|id| ([col.x_pos * 16 + 8, col.z_pos * 16 + 8], id.to_string()))
+                .collect()
+            ).unwrap_or_default();
+            let fallback = self.y_level.is_none() && !self.ceiling && col.heightmaps.get(self.heightmap.key()).is_none();
+            Ok(Some((col.x_pos, col.z_pos, col.data_version, pixels, south_row, unknown_blocks, banners, block_counts, structures, fallback)))
+        }).collect::<Result<Vec<_>, Error>>()?;
+        let mut img = RgbaImage::new(16 * 32, 16 * 32);
+        let mut region_unknown_blocks = BTreeSet::default();
+        let mut region_banners = BTreeMap::default();
+        let mut region_structures = Vec::default();
+        let mut region_block_counts = BTreeMap::<_, u64>::default();
+        let mut data_version = None;
+        let mut num_columns = 0;
+        let mut fallback_columns = 0;
+        let mut south_surfaces = Box::new([None; 16 * 32]);
+        for (x_pos, z_pos, col_data_version, pixels, south_row, unknown_blocks, banners, block_counts, structures, fallback) in columns.into_iter().flatten() {
+            num_columns += 1;
+            if fallback {
+                fallback_columns += 1;
+            }
+            data_version = data_version.max(Some(col_data_version));
+            // the south edge is the bottom row of blocks of the southernmost row of chunk columns
+            if z_pos.rem_euclid(32) == 31 {
+                south_surfaces[x_pos.rem_euclid(32) as usize * 16..][..16].copy_from_slice(&south_row);
+            }
+            let slime_chunk = match (self.dimension, self.slime_chunks) {
+                (Dimension::Overworld, Some(seed)) => slime::is_slime_chunk(seed, [x_pos, z_pos]),
+                (_, _) => false,
+            };
+            for (block_z, row) in pixels.into_iter().enumerate() {
+                for (block_x, mut pixel) in row.into_iter().enumerate() {
+                    if slime_chunk {
+                        pixel.blend(&Rgba([0, 255, 0, 64]));
+                    }
+                    img[(x_pos.rem_euclid(32) as u32 * 16 + block_x as u32, z_pos.rem_euclid(32) as u32 * 16 + block_z as u32)] = pixel;
+                }
+            }
+            region_unknown_blocks.extend(unknown_blocks);
+            region_banners.extend(banners);
+            region_structures.extend(structures);
+            for (name, count) in block_counts {
+                *region_block_counts.entry(name).or_default() += count;
+            }
+        }
+        if let Some(grid_color) = self.grid {
+            draw_grid(&mut img, grid_color);
+        }
+        // markers are drawn last so they're always visible
+        if let Dimension::Overworld = self.dimension {
+            if let Some(border) = &self.world_border {
+                markers::draw_border(&mut img, region.coords, border);
+            }
+            if let Some(spawn) = self.spawn_marker {
+                markers::draw_spawn(&mut img, region.coords, spawn);
+            }
+        }
+        for (coords, color) in region_banners {
+            markers::draw_banner(&mut img, region.coords, coords, color);
+        }
+        for (coords, id) in region_structures {
+            markers::draw_structure(&mut img, region.coords, coords, &id);
+        }
+        for player in &self.players {
+            if player.dimension == self.dimension {
+                markers::draw_player(&mut img, region.coords, player);
+            }
+        }
+        Ok(RenderedRegion { img, unknown_blocks: region_unknown_blocks, data_version, south_edge: SouthEdge { coords: region.coords, surfaces: south_surfaces }, block_counts: region_block_counts, columns: num_columns, fallback_columns })
+    }
+
+    /// Draws the block or sky light level right above the surface block of each column, e.g. to spot dark areas where mobs can spawn. Ungenerated chunks are transparent.
+    pub fn render_light_map(&self, region: &Region, kind: LightKind) -> Result<RgbaImage, Error> {
+        let [region_x, region_z] = region.coords;
+        // blocks missing from the color table are reported when the region itself is rendered
+        let mut unknown_blocks = BTreeSet::default();
+        let mut img = RgbaImage::new(16 * 32, 16 * 32);
+        for chunk_z in 0..32 {
+            for chunk_x in 0..32 {
+                let Some(col) = region.chunk_column([region_x * 32 + chunk_x, region_z * 32 + chunk_z])? else { continue };
+                for block_z in 0..16 {
+                    for block_x in 0..16 {
+                        let Some(surface) = self.find_surface(&mut unknown_blocks, &col, block_x, block_z) else { continue };
+                        // opaque blocks are always at light level 0, so like for mob spawning, the block above the surface is used
+                        let y = surface.y + 1;
+                        let data = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)).and_then(|chunk| match kind {
+                            LightKind::Block => chunk.block_light.as_deref(),
+                            LightKind::Sky => chunk.sky_light.as_deref(),
+                        });
+                        let level = data.map_or(kind.fallback(), |data| light::nibble(data, [block_x as u8, y.rem_euclid(16) as u8, block_z as u8]));
+                        img[(chunk_x as u32 * 16 + block_x as u32, chunk_z as u32 * 16 + block_z as u32)] = kind.color(level);
+                    }
+                }
+            }
+        }
+        Ok(img)
+    }
+
+    /// Loads and renders the region with the given coordinates from a world directory.
+    pub async fn render_region(self: &Arc<Self>, world_dir: &Path, coords: [i32; 2]) -> Result<RenderedRegion, Error> {
+        let [x, z] = coords;
+        let region = Region::find_no_diff(world_dir, self.dimension, coords, Vec::default()).await?.ok_or(Error::RegionNotFound)?;
+        // if the north neighbor can't be loaded, the northernmost row is shaded as if it were at the edge of the map
+        let prev = Region::find_no_diff(world_dir, self.dimension, [x, z - 1], Vec::default()).await.ok().flatten();
+        let west = if self.needs_west_neighbor() {
+            Region::find_no_diff(world_dir, self.dimension, [x - 1, z], Vec::default()).await.ok().flatten()
+        } else {
+            None
+        };
+        let renderer = Arc::clone(self);
+        Ok(tokio::task::spawn_blocking(move || {
+            let prev = prev.and_then(|prev| renderer.south_edge(&prev).ok());
+            let west = west.and_then(|west| renderer.east_edge(&west).ok());
+            renderer.render(&region, prev.as_ref(), west.as_ref())
+        }).await??)
+    }
+
+    /// Renders every region of this renderer's dimension in the given world directory, one at a time.
+    ///
+    /// The images are only yielded, so it's up to the caller to store, composite, or serve them.
+    pub fn render_all<'a>(self: &'a Arc<Self>, world_dir: &'a Path) -> impl Stream<Item = Result<([i32; 2], RenderedRegion), Error>> + 'a {
+        Region::all_coords(world_dir, self.dimension)
+            .map_err(Error::from)
+            .and_then(move |coords| async move { Ok((coords, self.render_region(world_dir, coords).await?)) })
+    }
+
+    /// Like [`Renderer::render_all`], but renders up to `jobs` regions concurrently and yields each one as soon as it's finished, so the order of the regions is unspecified.
+    ///
+    /// This is intended for frontends that display regions while the rest of the world is still rendering.
+    pub fn render_stream<'a>(self: &'a Arc<Self>, world_dir: &'a Path, jobs: NonZero<usize>) -> impl Stream<Item = Result<([i32; 2], RenderedRegion), Error>> + 'a {
+        Region::all_coords(world_dir, self.dimension)
+            .map_err(Error::from)
+            .map_ok(move |coords| async move { Ok((coords, self.render_region(world_dir, coords).await?)) })
+            .try_buffer_unordered(jobs.get())
+    }
+}
+
+/// Draws region boundaries and, more faintly, chunk boundaries on top of a rendered image.
+fn draw_grid(img: &mut RgbaImage, color: Rgba<u8>) {
+    let Rgba([r, g, b, _]) = color;
+    for (x, z, pixel) in img.enumerate_pixels_mut() {
+        let alpha = if x % (16 * 32) == 0 || z % (16 * 32) == 0 {
+            192
+        } else if x % 16 == 0 || z % 16 == 0 {
+            64
+        } else {
+            continue
+        };
+        pixel.blend(&Rgba([r, g, b, alpha]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        std::sync::LazyLock,
+        super::*,
+    };
+
+    /// Renders the fixture region described in `tests/fixtures/README.md`.
+    fn render_fixture(renderer: &Renderer) -> RenderedRegion {
+        let world_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join("world");
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let region = runtime.block_on(Region::find_no_diff(&world_dir, Dimension::Overworld, [0, 0], Vec::default())).unwrap().unwrap();
+        renderer.render(&region, None, None).unwrap()
+    }
+
+    /// The pixel of the block at `[x, z]` within the chunk column at `[chunk_x, chunk_z]` of a rendered region.
+    fn pixel(img: &RgbaImage, [chunk_x, chunk_z]: [u32; 2], [x, z]: [u32; 2]) -> Rgba<u8> {
+        *img.get_pixel(chunk_x * 16 + x, chunk_z * 16 + z)
+    }
+
+    fn default_renderer() -> Renderer {
+        Renderer::new(Dimension::Overworld, Arc::new(get_block_colors(Palette::Vanilla)))
+    }
+
+    /// The fixture region rendered with the default settings, shared by the tests that check individual scenarios so it's only decoded and rendered once.
+    static FIXTURE: LazyLock<RenderedRegion> = LazyLock::new(|| render_fixture(&default_renderer()));
+
+    #[test]
+    fn north_shading() {
+        let img = &FIXTURE.img;
+        // the top row of the region has no north neighbor
+        assert_eq!(pixel(img, [0, 0], [0, 0]), MapColor::Stone.tint(Tint::Normal));
+        assert_eq!(pixel(img, [0, 0], [0, 7]), MapColor::Stone.tint(Tint::Normal));
+        assert_eq!(pixel(img, [0, 0], [0, 8]), MapColor::Stone.tint(Tint::Dark));
+        assert_eq!(pixel(img, [0, 0], [0, 9]), MapColor::Stone.tint(Tint::Normal));
+        assert_eq!(pixel(img, [0, 0], [0, 12]), MapColor::Stone.tint(Tint::Light));
+        assert_eq!(pixel(img, [0, 0], [8, 8]), MapColor::Sand.tint(Tint::Dark));
+        assert_eq!(pixel(img, [0, 0], [8, 12]), MapColor::Sand.tint(Tint::Light));
+        // chunk columns that haven't been generated are transparent
+        assert_eq!(pixel(img, [1, 0], [0, 0]), Rgba([0; 4]));
+        assert_eq!(pixel(img, [0, 1], [0, 0]), Rgba([0; 4]));
+    }
+
+    #[test]
+    fn no_banners() {
+        // the fixture's chunk columns have empty block entity lists, so reading them must not change the map
+        let mut renderer = default_renderer();
+        renderer.banners = true;
+        assert_eq!(render_fixture(&renderer).img, FIXTURE.img);
+    }
+
+    #[test]
+    fn deep_and_iced_water() {
+        let img = &FIXTURE.img;
+        // 24 blocks deep
+        assert_eq!(pixel(img, [10, 0], [0, 0]), MapColor::Water.tint(Tint::Dark));
+        assert_eq!(pixel(img, [10, 0], [1, 0]), MapColor::Water.tint(Tint::Dark));
+        // the ice inside the water counts towards its depth, so this is 12 blocks deep rather than 4
+        assert_eq!(pixel(img, [10, 0], [8, 0]), MapColor::Water.tint(Tint::Dark));
+        assert_eq!(pixel(img, [10, 0], [9, 0]), MapColor::Water.tint(Tint::Dark));
+    }
+
+    #[test]
+    fn from_rgb_round_trip() {
+        for color in MapColor::ALL {
+            for tint in Tint::ALL {
+                let expected = (color != MapColor::None).then_some((color, tint));
+                assert_eq!(MapColor::from_rgb(color.tint(tint)), expected, "{color:?} {tint:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn from_rgb_rejects_other_colors() {
+        assert_eq!(MapColor::from_rgb(Rgba([0; 4])), None);
+        assert_eq!(MapColor::from_rgb(Rgba([255, 0, 255, u8::MAX])), None);
+    }
+
+    #[test]
+    fn vanilla_shades() {
+        for (shade, tint, multiplier) in [(0, Tint::Dark, 180), (1, Tint::Normal, 220), (2, Tint::Light, 255), (3, Tint::Darkest, 135)] {
+            assert_eq!(Tint::from_shade(shade), tint);
+            assert_eq!(tint.shade(), shade);
+            assert_eq!(tint.multiplier(), multiplier);
+        }
+    }
+
+    fn block(name: BlockId, properties: &[(&str, &str)]) -> BlockState {
+        BlockState { name, properties: properties.iter().map(|&(key, value)| (key.to_owned(), value.to_owned())).collect() }
+    }
+
+    #[test]
+    fn by_property_default() {
+        let color = BlockMapColor::ByProperty {
+            property: "open".to_owned(),
+            cases: vec![("true".to_owned(), MapColor::Wood)],
+            default: MapColor::Stone,
+        };
+        assert_eq!(resolve_color(&color, &block(BlockId::OakTrapdoor, &[("open", "true")])), MapColor::Wood);
+        assert_eq!(resolve_color(&color, &block(BlockId::OakTrapdoor, &[("open", "false")])), MapColor::Stone);
+        assert_eq!(resolve_color(&color, &block(BlockId::OakTrapdoor, &[("open", "ajar")])), MapColor::Stone);
+        assert_eq!(resolve_color(&color, &block(BlockId::OakTrapdoor, &[("facing", "north")])), MapColor::Stone);
+        assert_eq!(resolve_color(&color, &block(BlockId::OakTrapdoor, &[])), MapColor::Stone);
+    }
+
+    #[test]
+    fn unknown_blocks() {
+        let rendered = &*FIXTURE;
+        assert_eq!(rendered.unknown_blocks, BTreeSet::from(["wurstmapberg:unknown_block".to_owned()]));
+        // unknown blocks are skipped like air
+        assert_eq!(pixel(&rendered.img, [2, 0], [0, 0]), MapColor::Stone.tint(Tint::Normal));
+        // a column made entirely of unknown blocks is scanned to the bottom of the world without finding anything to draw
+        assert_eq!(pixel(&rendered.img, [2, 0], [8, 0]), Rgba([0; 4]));
+        assert_eq!(pixel(&rendered.img, [2, 0], [15, 15]), Rgba([0; 4]));
+    }
+
+    #[test]
+    fn barriers() {
+        let img = &FIXTURE.img;
+        // dry barriers are skipped, showing the stone below
+        assert_eq!(pixel(img, [4, 0], [0, 0]), MapColor::Stone.tint(Tint::Normal));
+        assert_eq!(pixel(img, [4, 0], [7, 15]), MapColor::Stone.tint(Tint::Normal));
+        // waterlogged barriers show their water, which is 1 block deep
+        assert_eq!(pixel(img, [4, 0], [8, 0]), MapColor::Water.tint(Tint::Light));
+    }
+
+    #[test]
+    fn custom_world_height() {
+        let img = &FIXTURE.img;
+        // the sand above Y 320 is found even though this chunk column has no heightmaps
+        assert_eq!(pixel(img, [12, 0], [0, 0]), MapColor::Sand.tint(Tint::Normal));
+        assert_eq!(pixel(img, [12, 0], [15, 15]), MapColor::Sand.tint(Tint::Normal));
+    }
+
+    #[test]
+    fn lily_pads() {
+        let img = &FIXTURE.img;
+        // without the water depth tint, the lily pads would be shaded dark since they're lower than the wall to their north
+        assert_eq!(pixel(img, [6, 0], [0, 1]), MapColor::Plant.tint(Tint::Normal));
+        assert_eq!(pixel(img, [6, 0], [7, 15]), MapColor::Plant.tint(Tint::Normal));
+        // the open water next to them is just as deep
+        assert_eq!(pixel(img, [6, 0], [8, 1]), MapColor::Water.tint(Tint::Normal));
+    }
+
+    #[test]
+    fn tall_plants() {
+        let img = &FIXTURE.img;
+        // the upper half is drawn with the height of the lower half, which is level with the ledge to the north
+        assert_eq!(pixel(img, [8, 0], [0, 1]), MapColor::Plant.tint(Tint::Normal));
+        assert_eq!(pixel(img, [8, 0], [7, 15]), MapColor::Plant.tint(Tint::Normal));
+        // next to the sunflowers, the ground is a block lower than the ledge
+        assert_eq!(pixel(img, [8, 0], [8, 1]), MapColor::Stone.tint(Tint::Dark));
+        assert_eq!(pixel(img, [8, 0], [8, 2]), MapColor::Stone.tint(Tint::Normal));
+    }
+}
@@ -0,0 +1,73 @@
+use {
+    image::Rgba,
+    mcanvil::{
+        BlockId,
+        BlockState,
+    },
+};
+
+/// Which of the light levels stored in chunk sections to show in a light map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum LightKind {
+    /// Light from light-emitting blocks. Since Minecraft 1.18, most hostile mobs can only spawn where this is 0.
+    Block,
+    /// Light from the sky, regardless of the time of day.
+    Sky,
+}
+
+impl LightKind {
+    /// The light level assumed where a chunk section doesn't store any light data.
+    pub(crate) fn fallback(self) -> u8 {
+        match self {
+            Self::Block => 0,
+            Self::Sky => 15,
+        }
+    }
+
+    /// Draws a light level in grayscale from black to white. Block light level 0 is drawn in red instead, since that's where mobs can spawn.
+    pub(crate) fn color(self, level: u8) -> Rgba<u8> {
+        if self == Self::Block && level == 0 { return Rgba([255, 0, 0, u8::MAX]) }
+        let brightness = level.min(15) * 17;
+        Rgba([brightness, brightness, brightness, u8::MAX])
+    }
+}
+
+/// Reads the value for the given block from a nibble array like the `BlockLight` and `SkyLight` tags of a chunk section, which store two 4-bit values per byte in YZX order. A truncated array is treated as having no light for the missing blocks.
+pub(crate) fn nibble(data: &[i8], [x, y, z]: [u8; 3]) -> u8 {
+    let index = usize::from(y) * 16 * 16 + usize::from(z) * 16 + usize::from(x);
+    let Some(&byte) = data.get(index / 2) else { return 0 };
+    let byte = byte as u8;
+    if index % 2 == 0 { byte & 0xf } else { byte >> 4 }
+}
+
+/// The light level emitted by the given block, based on <https://minecraft.wiki/w/Light#Light-emitting_blocks>. Blocks not listed here don't emit light.
+pub(crate) fn light_level(block: &BlockState) -> u8 {
+    let lit = || block.properties.get("lit").is_some_and(|lit| lit == "true");
+    let property = |name: &str| block.properties.get(name).and_then(|value| value.parse::<u8>().ok());
+    match block.name {
+        BlockId::Beacon | BlockId::Conduit | BlockId::CopperLantern | BlockId::EndGateway | BlockId::EndPortal | BlockId::Fire | BlockId::Glowstone | BlockId::JackOLantern | BlockId::Lantern | BlockId::Lava | BlockId::LavaCauldron | BlockId::OchreFroglight | BlockId::PearlescentFroglight | BlockId::SeaLantern | BlockId::Shroomlight | BlockId::VerdantFroglight => 15,
+        BlockId::CopperTorch | BlockId::CopperWallTorch | BlockId::EndRod | BlockId::Torch | BlockId::WallTorch => 14,
+        BlockId::NetherPortal => 11,
+        BlockId::CryingObsidian | BlockId::SoulFire | BlockId::SoulLantern | BlockId::SoulTorch | BlockId::SoulWallTorch => 10,
+        BlockId::GlowLichen => 7,
+        BlockId::AmethystCluster => 5,
+        BlockId::LargeAmethystBud => 4,
+        BlockId::MagmaBlock => 3,
+        BlockId::MediumAmethystBud => 2,
+        BlockId::BrewingStand | BlockId::DragonEgg | BlockId::EndPortalFrame | BlockId::SculkCatalyst | BlockId::SmallAmethystBud => 1,
+        BlockId::Campfire | BlockId::RedstoneLamp | BlockId::CopperBulb | BlockId::WaxedCopperBulb => if lit() { 15 } else { 0 },
+        BlockId::BlastFurnace | BlockId::Furnace | BlockId::Smoker => if lit() { 13 } else { 0 },
+        BlockId::ExposedCopperBulb | BlockId::WaxedExposedCopperBulb => if lit() { 12 } else { 0 },
+        BlockId::SoulCampfire => if lit() { 10 } else { 0 },
+        BlockId::WeatheredCopperBulb | BlockId::WaxedWeatheredCopperBulb => if lit() { 8 } else { 0 },
+        BlockId::RedstoneTorch | BlockId::RedstoneWallTorch => if lit() { 7 } else { 0 },
+        BlockId::OxidizedCopperBulb | BlockId::WaxedOxidizedCopperBulb => if lit() { 4 } else { 0 },
+        BlockId::Candle | BlockId::BlackCandle | BlockId::BlueCandle | BlockId::BrownCandle | BlockId::CyanCandle | BlockId::GrayCandle | BlockId::GreenCandle | BlockId::LightBlueCandle | BlockId::LightGrayCandle | BlockId::LimeCandle | BlockId::MagentaCandle | BlockId::OrangeCandle | BlockId::PinkCandle | BlockId::PurpleCandle | BlockId::RedCandle | BlockId::WhiteCandle | BlockId::YellowCandle => if lit() { 3 * property("candles").unwrap_or(1) } else { 0 },
+        BlockId::CandleCake | BlockId::BlackCandleCake | BlockId::BlueCandleCake | BlockId::BrownCandleCake | BlockId::CyanCandleCake | BlockId::GrayCandleCake | BlockId::GreenCandleCake | BlockId::LightBlueCandleCake | BlockId::LightGrayCandleCake | BlockId::LimeCandleCake | BlockId::MagentaCandleCake | BlockId::OrangeCandleCake | BlockId::PinkCandleCake | BlockId::PurpleCandleCake | BlockId::RedCandleCake | BlockId::WhiteCandleCake | BlockId::YellowCandleCake => if lit() { 3 } else { 0 },
+        BlockId::SeaPickle => if block.properties.get("waterlogged").is_some_and(|waterlogged| waterlogged == "true") { 3 * (property("pickles").unwrap_or(1) + 1) } else { 0 },
+        BlockId::RespawnAnchor => property("charges").map_or(0, |charges| (4 * charges.min(4)).saturating_sub(1)),
+        BlockId::Light => property("level").unwrap_or(15),
+        _ => 0,
+    }
+}
@@ -0,0 +1,112 @@
+use {
+    std::path::Path,
+    image::{
+        Rgba,
+        RgbaImage,
+    },
+    serde::Deserialize,
+    crate::{
+        Error,
+        MapColor,
+        Tint,
+    },
+};
+
+#[derive(Deserialize)]
+struct MapDat {
+    data: MapData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MapData {
+    colors: Vec<i8>,
+    scale: i8,
+    x_center: i32,
+    z_center: i32,
+    dimension: String,
+}
+
+/// An in-game map item, read from a `map_<id>.dat` file in the world's `data` directory.
+#[derive(Debug, Clone)]
+pub struct MapItem {
+    /// The number in the file name, which is also shown on the item in-game.
+    pub id: u32,
+    /// The dimension the map shows, as a namespaced ID like `minecraft:overworld`.
+    pub dimension: String,
+    /// The map is `128 * 2^scale` blocks wide.
+    pub scale: i8,
+    /// The block coordinates at the center of the map as `[x, z]`.
+    pub center: [i32; 2],
+    /// The color of each pixel, indexed as `[z][x]`. `None` for pixels that haven't been explored.
+    pixels: Box<[[Option<(MapColor, Tint)>; 128]; 128]>,
+}
+
+impl MapItem {
+    /// Reads all map items in the given world directory, sorted by ID. A world without a `data` directory has no map items.
+    pub async fn read_all(world_dir: &Path) -> Result<Vec<Self>, Error> {
+        let dir = world_dir.join("data");
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::default()),
+            Err(source) => return Err(Error::MapDataIo { path: dir, source }),
+        };
+        let mut maps = Vec::default();
+        while let Some(entry) = entries.next_entry().await.map_err(|source| Error::MapDataIo { path: dir.clone(), source })? {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "dat") { continue }
+            let Some(id) = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.strip_prefix("map_")).and_then(|id| id.parse().ok()) else { continue };
+            let buf = tokio::fs::read(&path).await.map_err(|source| Error::MapDataIo { path: path.clone(), source })?;
+            let MapDat { data } = nbt::from_gzip_reader(&*buf).map_err(|source| Error::MapData { path: path.clone(), source })?;
+            if data.colors.len() != 128 * 128 { return Err(Error::MapDataColors { path }) }
+            let mut pixels = Box::new([[None; 128]; 128]);
+            for (i, &color) in data.colors.iter().enumerate() {
+                let Some((map_color, tint)) = MapColor::from_map_byte(color as u8) else { return Err(Error::MapDataColors { path }) };
+                if map_color == MapColor::None { continue }
+                pixels[i / 128][i % 128] = Some((map_color, tint));
+            }
+            maps.push(Self { id, dimension: data.dimension, scale: data.scale, center: [data.x_center, data.z_center], pixels });
+        }
+        maps.sort_by_key(|map| map.id);
+        Ok(maps)
+    }
+
+    /// Draws the map the way it appears in-game, with unexplored pixels left transparent.
+    pub fn render(&self) -> RgbaImage {
+        RgbaImage::from_fn(128, 128, |x, z| match self.pixels[z as usize][x as usize] {
+            Some((color, tint)) => color.tint(tint),
+            None => Rgba([0; 4]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        MapColor,
+        Tint,
+    };
+
+    #[test]
+    fn map_byte_round_trip() {
+        for byte in 0..=u8::MAX {
+            match MapColor::from_map_byte(byte) {
+                Some((color, tint)) => assert_eq!(color.to_map_byte(tint), byte),
+                // IDs past the last map color are reserved
+                None => assert!(usize::from(byte / 4) >= MapColor::ALL.len(), "byte {byte} rejected"),
+            }
+        }
+        for color in MapColor::ALL {
+            for tint in Tint::ALL {
+                assert_eq!(MapColor::from_map_byte(color.to_map_byte(tint)), Some((color, tint)));
+            }
+        }
+    }
+
+    #[test]
+    fn reserved_map_bytes() {
+        for byte in 248..=u8::MAX {
+            assert_eq!(MapColor::from_map_byte(byte), None);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use {
+    std::collections::BTreeMap,
+    mcanvil::{
+        BlockId,
+        ChunkColumn,
+        Region,
+    },
+    image::{
+        Rgba,
+        RgbaImage,
+    },
+    serde::Deserialize,
+    crate::{
+        Error,
+        MapColor,
+        Player,
+        Tint,
+        WorldBorder,
+        section_index,
+    },
+};
+
+#[derive(Deserialize)]
+struct ChunkBlockEntities {
+    #[serde(default)]
+    block_entities: Vec<BlockEntity>,
+}
+
+#[derive(Deserialize)]
+struct BlockEntity {
+    id: String,
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+/// Converts block coordinates to pixel coordinates within the image of the given region, if they're inside it.
+fn pixel_coords([region_x, region_z]: [i32; 2], [x, z]: [i64; 2]) -> Option<(u32, u32)> {
+    let px = u32::try_from(x - i64::from(region_x) * 16 * 32).ok().filter(|&px| px < 16 * 32)?;
+    let pz = u32::try_from(z - i64::from(region_z) * 16 * 32).ok().filter(|&pz| pz < 16 * 32)?;
+    Some((px, pz))
+}
+
+/// Draws a diamond-shaped marker centered on the world spawn point. Parts outside of this region are skipped, so a marker near a region boundary is completed by the neighboring region.
+pub(crate) fn draw_spawn(img: &mut RgbaImage, region_coords: [i32; 2], [x, _, z]: [i32; 3]) {
+    const RADIUS: i64 = 4;
+
+    for dz in -RADIUS..=RADIUS {
+        for dx in -RADIUS..=RADIUS {
+            let distance = dx.abs() + dz.abs();
+            if distance > RADIUS { continue }
+            let Some(pixel) = pixel_coords(region_coords, [i64::from(x) + dx, i64::from(z) + dz]) else { continue };
+            img[pixel] = if distance == RADIUS { Rgba([255, 255, 255, 255]) } else { Rgba([255, 0, 0, 255]) };
+        }
+    }
+}
+
+/// Outlines the part of the world border rectangle that's inside this region.
+pub(crate) fn draw_border(img: &mut RgbaImage, [region_x, region_z]: [i32; 2], border: &WorldBorder) {
+    let [center_x, center_z] = border.center;
+    let min_x = (center_x - border.size / 2.0).floor() as i64;
+    let max_x = (center_x + border.size / 2.0).ceil() as i64 - 1;
+    let min_z = (center_z - border.size / 2.0).floor() as i64;
+    let max_z = (center_z + border.size / 2.0).ceil() as i64 - 1;
+    for (px, pz, pixel) in img.enumerate_pixels_mut() {
+        let x = i64::from(region_x) * 16 * 32 + i64::from(px);
+        let z = i64::from(region_z) * 16 * 32 + i64::from(pz);
+        let on_vertical_edge = (x == min_x || x == max_x) && (min_z..=max_z).contains(&z);
+        let on_horizontal_edge = (z == min_z || z == max_z) && (min_x..=max_x).contains(&x);
+        if on_vertical_edge || on_horizontal_edge {
+            *pixel = Rgba([255, 0, 0, 255]);
+        }
+    }
+}
+
+/// Draws a square marker in the player's color at the player's position.
+pub(crate) fn draw_player(img: &mut RgbaImage, region_coords: [i32; 2], player: &Player) {
+    const RADIUS: i64 = 3;
+
+    let [x, _, z] = player.pos;
+    let [r, g, b] = player.color();
+    for dz in -RADIUS..=RADIUS {
+        for dx in -RADIUS..=RADIUS {
+            let Some(pixel) = pixel_coords(region_coords, [x.floor() as i64 + dx, z.floor() as i64 + dz]) else { continue };
+            img[pixel] = if dx.abs() == RADIUS || dz.abs() == RADIUS { Rgba([0, 0, 0, 255]) } else { Rgba([r, g, b, 255]) };
+        }
+    }
+}
+
+/// The color of the banner marker for the given block, if it's a banner.
+fn banner_color(id: &BlockId) -> Option<MapColor> {
+    Some(match id {
+        BlockId::WhiteBanner | BlockId::WhiteWallBanner => MapColor::Snow,
+        BlockId::OrangeBanner | BlockId::OrangeWallBanner => MapColor::ColorOrange,
+        BlockId::MagentaBanner | BlockId::MagentaWallBanner => MapColor::ColorMagenta,
+        BlockId::LightBlueBanner | BlockId::LightBlueWallBanner => MapColor::ColorLightBlue,
+        BlockId::YellowBanner | BlockId::YellowWallBanner => MapColor::ColorYellow,
+        BlockId::LimeBanner | BlockId::LimeWallBanner => MapColor::ColorLightGreen,
+        BlockId::PinkBanner | BlockId::PinkWallBanner => MapColor::ColorPink,
+        BlockId::GrayBanner | BlockId::GrayWallBanner => MapColor::ColorGray,
+        BlockId::LightGrayBanner | BlockId::LightGrayWallBanner => MapColor::ColorLightGray,
+        BlockId::CyanBanner | BlockId::CyanWallBanner => MapColor::ColorCyan,
+        BlockId::PurpleBanner | BlockId::PurpleWallBanner => MapColor::ColorPurple,
+        BlockId::BlueBanner | BlockId::BlueWallBanner => MapColor::ColorBlue,
+        BlockId::BrownBanner | BlockId::BrownWallBanner => MapColor::ColorBrown,
+        BlockId::GreenBanner | BlockId::GreenWallBanner => MapColor::ColorGreen,
+        BlockId::RedBanner | BlockId::RedWallBanner => MapColor::ColorRed,
+        BlockId::BlackBanner | BlockId::BlackWallBanner => MapColor::ColorBlack,
+        _ => return None,
+    })
+}
+
+/// Reads the block entities of a chunk column from the raw region file, since they're not part of the decoded [`ChunkColumn`].
+fn block_entities(region: &Region, [chunk_x, chunk_z]: [i32; 2]) -> Result<Vec<BlockEntity>, Error> {
+    // each entry of the location table is a 3-byte offset in 4 KiB sectors followed by a 1-byte sector count
+    let entry = 4 * (chunk_x.rem_euclid(32) + chunk_z.rem_euclid(32) * 32) as usize;
+    let Some(&[a, b, c, _]) = region.buf.get(entry..entry + 4) else { return Ok(Vec::default()) };
+    let offset = u32::from_be_bytes([0, a, b, c]) as usize * 4096;
+    if offset == 0 { return Ok(Vec::default()) }
+    let Some(&[l1, l2, l3, l4, compression]) = region.buf.get(offset..offset + 5) else { return Ok(Vec::default()) };
+    // the length includes the compression type byte; truncated data is reported as a decoding error
+    let len = u32::from_be_bytes([l1, l2, l3, l4]) as usize;
+    let data = region.buf.get(offset + 5..(offset + 4 + len).min(region.buf.len())).unwrap_or_default();
+    let ChunkBlockEntities { block_entities } = match compression {
+        1 => nbt::from_gzip_reader(data),
+        2 => nbt::from_zlib_reader(data),
+        3 => nbt::from_reader(data),
+        // LZ4 and custom compression aren't supported by hematite-nbt, so banners in such chunks aren't marked
+        _ => return Ok(Vec::default()),
+    }.map_err(|source| Error::BlockEntities { chunk: [chunk_x, chunk_z], source })?;
+    Ok(block_entities)
+}
+
+/// Finds the banners placed in a chunk column, keyed by their `[x, z]` block coordinates. If several banners are stacked in the same column of blocks, the color of the topmost one is used.
+pub(crate) fn banners(region: &Region, col: &ChunkColumn) -> Result<BTreeMap<[i32; 2], MapColor>, Error> {
+    let mut banners = BTreeMap::<_, (i32, MapColor)>::default();
+    for BlockEntity { id, x, y, z } in block_entities(region, [col.x_pos, col.z_pos])? {
+        if id != "minecraft:banner" { continue }
+        let Some(chunk) = section_index(y).and_then(|chunk_y| col.section_at(chunk_y)) else { continue };
+        // the base color is part of the block ID rather than the block entity
+        let Some(color) = banner_color(&chunk.block_relative([x.rem_euclid(16) as u8, y.rem_euclid(16) as u8, z.rem_euclid(16) as u8]).name) else { continue };
+        if banners.get(&[x, z]).is_none_or(|&(top, _)| y > top) {
+            banners.insert([x, z], (y, color));
+        }
+    }
+    Ok(banners.into_iter().map(|(pos, (_, color))| (pos, color)).collect())
+}
+
+/// Draws a flag-shaped marker in the banner's color, with its pole at the banner's position.
+pub(crate) fn draw_banner(img: &mut RgbaImage, region_coords: [i32; 2], [x, z]: [i32; 2], color: MapColor) {
+    let Rgba([r, g, b, _]) = color.tint(Tint::Light);
+    for dz in -6..=0 {
+        for dx in -2..=2 {
+            let Some(pixel) = pixel_coords(region_coords, [i64::from(x) + dx, i64::from(z) + dz]) else { continue };
+            img[pixel] = match (dx, dz) {
+                (0, -1..=0) => Rgba([0, 0, 0, 255]), // pole
+                (-1..=1, -5..=-3) => Rgba([r, g, b, 255]),
+                (-2 | 2, -6..=-2) | (_, -6 | -2) => Rgba([0, 0, 0, 255]), // outline
+                (_, _) => continue,
+            };
+        }
+    }
+}
+
+/// Whether the structure with the given ID is selected by the filters of [`Renderer::structures`](crate::Renderer::structures).
+pub(crate) fn structure_selected(filters: &[String], id: &str) -> bool {
+    let path = id.strip_prefix("minecraft:").unwrap_or(id);
+    filters.is_empty() || filters.iter().any(|filter| filter == id || path.starts_with(&**filter))
+}
+
+/// Draws a round marker at the given position, in a color derived from the structure ID so that different types of structures can be told apart.
+pub(crate) fn draw_structure(img: &mut RgbaImage, region_coords: [i32; 2], [x, z]: [i32; 2], id: &str) {
+    const RADIUS: i64 = 4;
+
+    let hash = id.bytes().fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte.into()));
+    let [_, r, g, b] = hash.to_be_bytes();
+    for dz in -RADIUS..=RADIUS {
+        for dx in -RADIUS..=RADIUS {
+            let distance_squared = dx * dx + dz * dz;
+            if distance_squared > RADIUS * RADIUS { continue }
+            let Some(pixel) = pixel_coords(region_coords, [i64::from(x) + dx, i64::from(z) + dz]) else { continue };
+            img[pixel] = if distance_squared > (RADIUS - 1) * (RADIUS - 1) { Rgba([0, 0, 0, 255]) } else { Rgba([r, g, b, 255]) };
+        }
+    }
+}
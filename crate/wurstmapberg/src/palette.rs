@@ -0,0 +1,109 @@
+use {
+    std::collections::HashMap,
+    collect_mac::collect,
+    mcanvil::BlockId,
+    crate::{
+        BlockMapColor::{
+            self,
+            *,
+        },
+        MapColor::*,
+        colors::vanilla_block_colors,
+    },
+};
+
+/// A built-in color table.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Palette {
+    /// The map colors used by the game.
+    #[default]
+    Vanilla,
+    /// The vanilla colors with the overrides used for the Wurstmineberg server's maps.
+    Wurstmine,
+}
+
+/// Overrides applied on top of the vanilla colors for [`Palette::Wurstmine`], in the same format as the built-in color table.
+///
+/// Water is drawn in the plain map color instead of being tinted by biome, so rivers and oceans have the same blue everywhere. Dyed terracotta uses the full dye colors of concrete and glazed terracotta rather than the muted terracotta colors, so builds stand out from badlands terrain.
+fn wurstmine_overrides() -> HashMap<BlockId, BlockMapColor> {
+    collect![
+        BlockId::BubbleColumn => Single(Water),
+        BlockId::Water => Single(Water),
+        BlockId::BlackTerracotta => Single(ColorBlack),
+        BlockId::BlueTerracotta => Single(ColorBlue),
+        BlockId::BrownTerracotta => Single(ColorBrown),
+        BlockId::CyanTerracotta => Single(ColorCyan),
+        BlockId::GrayTerracotta => Single(ColorGray),
+        BlockId::GreenTerracotta => Single(ColorGreen),
+        BlockId::LightBlueTerracotta => Single(ColorLightBlue),
+        BlockId::LightGrayTerracotta => Single(ColorLightGray),
+        BlockId::LimeTerracotta => Single(ColorLightGreen),
+        BlockId::MagentaTerracotta => Single(ColorMagenta),
+        BlockId::OrangeTerracotta => Single(ColorOrange),
+        BlockId::PinkTerracotta => Single(ColorPink),
+        BlockId::PurpleTerracotta => Single(ColorPurple),
+        BlockId::RedTerracotta => Single(ColorRed),
+        BlockId::WhiteTerracotta => Single(Snow),
+        BlockId::YellowTerracotta => Single(ColorYellow),
+    ]
+}
+
+/// Returns the color table of the given built-in palette. Palettes other than vanilla fall back to the vanilla color for blocks they don't override.
+pub fn get_block_colors(palette: Palette) -> HashMap<BlockId, BlockMapColor> {
+    let mut block_colors = vanilla_block_colors();
+    match palette {
+        Palette::Vanilla => {}
+        Palette::Wurstmine => block_colors.extend(wurstmine_overrides()),
+    }
+    block_colors
+}
+
+/// The IDs of all vanilla blocks as of Minecraft 26.1.2, one per line and without the `minecraft:` namespace. Maintained separately from the generated color table, e.g. from the `blocks.json` report of Minecraft's data generator, so that blocks the color extraction misses show up in [`uncovered_blocks`].
+const VANILLA_BLOCKS: &str = include_str!("vanilla_blocks.txt");
+
+/// Returns the IDs of the vanilla blocks that have no entry in the given color table, in alphabetical order. Useful for checking palette completeness after a Minecraft update.
+pub fn uncovered_blocks(block_colors: &HashMap<BlockId, BlockMapColor>) -> Vec<&'static str> {
+    VANILLA_BLOCKS.lines().filter(|id| {
+        let Ok(block_id) = format!("minecraft:{id}").parse::<BlockId>();
+        !block_colors.contains_key(&block_id)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        mcanvil::BlockId,
+        crate::{
+            BlockMapColor,
+            MapColor,
+        },
+        super::{
+            Palette,
+            get_block_colors,
+            uncovered_blocks,
+        },
+    };
+
+    /// How many vanilla blocks may be missing from the built-in color table: `cave_air` and `void_air`, which are never drawn. Lower this when coverage improves.
+    const MAX_UNCOVERED: usize = 2;
+
+    #[test]
+    fn wurstmine_falls_back_to_vanilla() {
+        let vanilla = get_block_colors(Palette::Vanilla);
+        let wurstmine = get_block_colors(Palette::Wurstmine);
+        // overrides only replace colors, they don't add blocks
+        assert_eq!(wurstmine.len(), vanilla.len());
+        assert!(matches!(vanilla[&BlockId::Water], BlockMapColor::Biome { .. }));
+        assert!(matches!(wurstmine[&BlockId::Water], BlockMapColor::Single(MapColor::Water)));
+        assert!(matches!(vanilla[&BlockId::RedTerracotta], BlockMapColor::Single(MapColor::TerracottaRed)));
+        assert!(matches!(wurstmine[&BlockId::RedTerracotta], BlockMapColor::Single(MapColor::ColorRed)));
+        assert!(matches!(wurstmine[&BlockId::Stone], BlockMapColor::Single(MapColor::Stone)));
+    }
+
+    #[test]
+    fn coverage() {
+        let uncovered = uncovered_blocks(&get_block_colors(Palette::Vanilla));
+        assert!(uncovered.len() <= MAX_UNCOVERED, "{} vanilla blocks are missing from the color table: {}", uncovered.len(), uncovered.join(", "));
+    }
+}
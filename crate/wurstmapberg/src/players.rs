@@ -0,0 +1,59 @@
+use {
+    std::path::Path,
+    mcanvil::Dimension,
+    serde::Deserialize,
+    crate::Error,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PlayerDat {
+    pos: Vec<f64>,
+    dimension: String,
+}
+
+/// A player's last known position, from the `playerdata` directory.
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub uuid: String,
+    pub dimension: Dimension,
+    /// The position as `[x, y, z]`.
+    pub pos: [f64; 3],
+}
+
+impl Player {
+    /// Reads all players from the `playerdata` directory in the given world directory. Players in dimensions other than the Overworld, Nether, and End are skipped.
+    pub async fn read_all(world_dir: &Path) -> Result<Vec<Self>, Error> {
+        let dir = world_dir.join("playerdata");
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::default()),
+            Err(source) => return Err(Error::PlayerDataIo { path: dir, source }),
+        };
+        let mut players = Vec::default();
+        while let Some(entry) = entries.next_entry().await.map_err(|source| Error::PlayerDataIo { path: dir.clone(), source })? {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "dat") { continue }
+            let Some(uuid) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            let uuid = uuid.to_owned();
+            let buf = tokio::fs::read(&path).await.map_err(|source| Error::PlayerDataIo { path: path.clone(), source })?;
+            let PlayerDat { pos, dimension } = nbt::from_gzip_reader(&*buf).map_err(|source| Error::PlayerData { path: path.clone(), source })?;
+            let dimension = match &*dimension {
+                "minecraft:overworld" => Dimension::Overworld,
+                "minecraft:the_nether" => Dimension::Nether,
+                "minecraft:the_end" => Dimension::End,
+                _ => continue,
+            };
+            let Ok(pos) = <[f64; 3]>::try_from(pos) else { return Err(Error::PlayerDataPos { path }) };
+            players.push(Self { uuid, dimension, pos });
+        }
+        Ok(players)
+    }
+
+    /// A color derived from the player's UUID, so that each player's marker can be told apart.
+    pub(crate) fn color(&self) -> [u8; 3] {
+        let hash = self.uuid.bytes().fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte.into()));
+        let [_, r, g, b] = hash.to_be_bytes();
+        [r, g, b]
+    }
+}
@@ -0,0 +1,25 @@
+/// A port of `java.util.Random::nextInt(int)`, seeded with `seed`.
+fn java_next_int(seed: i64, bound: i32) -> i32 {
+    const MULTIPLIER: i64 = 0x5deece66d;
+    const MASK: i64 = (1 << 48) - 1;
+
+    let mut seed = (seed ^ MULTIPLIER) & MASK;
+    loop {
+        seed = seed.wrapping_mul(MULTIPLIER).wrapping_add(0xb) & MASK;
+        let bits = (seed >> (48 - 31)) as i32;
+        let value = bits % bound;
+        // reject values from the incomplete last interval to avoid bias, like Java does
+        if bits.wrapping_sub(value).wrapping_add(bound - 1) >= 0 { return value }
+    }
+}
+
+/// Whether slimes can spawn in the given Overworld chunk (outside of swamps), using the same algorithm as the game.
+pub(crate) fn is_slime_chunk(seed: i64, [x, z]: [i32; 2]) -> bool {
+    let seed = seed
+        .wrapping_add(i64::from(x.wrapping_mul(x).wrapping_mul(0x4c1906)))
+        .wrapping_add(i64::from(x.wrapping_mul(0x5ac0db)))
+        .wrapping_add(i64::from(z.wrapping_mul(z)).wrapping_mul(0x4307a7))
+        .wrapping_add(i64::from(z.wrapping_mul(0x5f24f)))
+        ^ 0x3ad8025f;
+    java_next_int(seed, 10) == 0
+}
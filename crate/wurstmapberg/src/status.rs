@@ -0,0 +1,51 @@
+use {
+    image::{
+        Rgba,
+        RgbaImage,
+    },
+    mcanvil::Region,
+    crate::Error,
+};
+
+/// The generation steps of a chunk column, in the order they're performed. See <https://minecraft.wiki/w/Chunk_format#NBT_structure>.
+const STATUSES: [&str; 12] = [
+    "empty",
+    "structure_starts",
+    "structure_references",
+    "biomes",
+    "noise",
+    "surface",
+    "carvers",
+    "features",
+    "initialize_light",
+    "light",
+    "spawn",
+    "full",
+];
+
+/// Colors generation statuses from red for chunks that have barely started generating to green for fully generated chunks. Unknown statuses are magenta.
+fn status_color(status: &str) -> Rgba<u8> {
+    let status = status.strip_prefix("minecraft:").unwrap_or(status);
+    let Some(step) = STATUSES.iter().position(|&known| known == status) else { return Rgba([255, 0, 255, u8::MAX]) };
+    let fraction = step as f64 / (STATUSES.len() - 1) as f64;
+    let [r, g] = if fraction < 0.5 { [1.0, fraction * 2.0] } else { [2.0 - fraction * 2.0, 1.0] }.map(|channel| (channel * 255.0).round() as u8);
+    Rgba([r, g, 0, u8::MAX])
+}
+
+/// Draws each chunk of the region in a color representing how far its generation has progressed. Chunks that aren't saved in the region file at all are transparent.
+pub fn render_chunk_status(region: &Region) -> Result<RgbaImage, Error> {
+    let [region_x, region_z] = region.coords;
+    let mut img = RgbaImage::new(16 * 32, 16 * 32);
+    for chunk_z in 0..32 {
+        for chunk_x in 0..32 {
+            let Some(col) = region.chunk_column([region_x * 32 + chunk_x, region_z * 32 + chunk_z])? else { continue };
+            let color = status_color(&col.status);
+            for block_z in 0..16 {
+                for block_x in 0..16 {
+                    img[(chunk_x as u32 * 16 + block_x, chunk_z as u32 * 16 + block_z)] = color;
+                }
+            }
+        }
+    }
+    Ok(img)
+}
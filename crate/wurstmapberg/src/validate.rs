@@ -0,0 +1,20 @@
+use {
+    mcanvil::Region,
+    rayon::prelude::*,
+    crate::{
+        Error,
+        MIN_DATA_VERSION,
+    },
+};
+
+/// Decodes every chunk column of the region without rendering it, returning the first error encountered, if any.
+pub fn validate_region(region: &Region) -> Result<(), Error> {
+    let [region_x, region_z] = region.coords;
+    (0..32 * 32).into_par_iter().try_for_each(|i| {
+        let Some(col) = region.chunk_column([region_x * 32 + i % 32, region_z * 32 + i / 32])? else { return Ok(()) };
+        if col.data_version < MIN_DATA_VERSION {
+            return Err(Error::UnsupportedDataVersion { chunk: [col.x_pos, col.z_pos], data_version: col.data_version })
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,44 @@
+#![allow(unused_crate_dependencies)] // dependencies of the library are also available to its tests
+
+use {
+    std::{
+        env,
+        path::Path,
+        sync::Arc,
+    },
+    image::RgbaImage,
+    mcanvil::Dimension,
+    wurstmapberg::{
+        Palette,
+        Renderer,
+        get_block_colors,
+    },
+};
+
+/// If this environment variable is set, the reference images are overwritten with the current output instead of being compared against it.
+const UPDATE_VAR: &str = "WURSTMAPBERG_UPDATE_SNAPSHOTS";
+
+#[test]
+fn render_region_snapshot() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures");
+    let renderer = Arc::new(Renderer::new(Dimension::Overworld, Arc::new(get_block_colors(Palette::Vanilla))));
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let rendered = runtime.block_on(renderer.render_region(&fixtures.join("world"), [0, 0])).unwrap();
+    assert!(rendered.unknown_blocks.contains("wurstmapberg:unknown_block"));
+    let snapshot_path = fixtures.join("r.0.0.png");
+    if env::var_os(UPDATE_VAR).is_some() {
+        rendered.img.save(&snapshot_path).unwrap();
+        return
+    }
+    let expected = RgbaImage::from(image::open(&snapshot_path).unwrap());
+    assert_eq!(rendered.img.dimensions(), expected.dimensions());
+    let mismatches = rendered.img.enumerate_pixels().filter(|&(x, z, pixel)| expected.get_pixel(x, z) != pixel).collect::<Vec<_>>();
+    if let Some(&(x, z, pixel)) = mismatches.first() {
+        panic!(
+            "{} pixels differ from {}, starting at {x}, {z}: expected {:?} but got {pixel:?}. If this change is intended, rerun with {UPDATE_VAR}=1 to update the reference image.",
+            mismatches.len(),
+            snapshot_path.display(),
+            expected.get_pixel(x, z),
+        );
+    }
+}